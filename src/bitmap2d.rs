@@ -0,0 +1,125 @@
+const BITS: usize = u64::BITS as usize;
+
+/// Represents a 2D grid of boolean values, packed into bits.
+/// Neighbors of a position are available through the [`Grid`](crate::grid::Grid) trait.
+pub struct BitMap2D {
+    bits: Vec<u64>,
+    width: i32,
+    height: i32,
+}
+
+impl BitMap2D {
+    /// Create a new map with all tiles set to false.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::bitmap2d::BitMap2D::new(100, 100);
+    /// assert_eq!(map.get(99, 99), Some(false));
+    /// assert_eq!(map.count_ones(), 0);
+    /// ```
+    pub fn new(width: i32, height: i32) -> BitMap2D {
+        let len = (width * height) as usize;
+        BitMap2D {
+            bits: vec![0; len.div_ceil(BITS)],
+            width,
+            height,
+        }
+    }
+
+    /// Get the value at the given position.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut map = aoc_lib::bitmap2d::BitMap2D::new(5, 4);
+    /// map.set(4, 3, true);
+    /// assert_eq!(map.get(4, 3), Some(true));
+    /// assert_eq!(map.get(3, 3), Some(false));
+    /// assert_eq!(map.get(5, 3), None);
+    /// ```
+    pub fn get(&self, x: i32, y: i32) -> Option<bool> {
+        match self.is_in_bounds(x, y) {
+            true => {
+                let idx = self.get_index(x, y);
+                Some(self.bits[idx / BITS] & (1 << (idx % BITS)) != 0)
+            }
+            false => None,
+        }
+    }
+
+    /// Set a position to the given value. Coordinates out of bounds will be ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut map = aoc_lib::bitmap2d::BitMap2D::new(5, 4);
+    /// map.set(1, 2, true);
+    /// assert_eq!(map.get(1, 2), Some(true));
+    /// map.set(1, 2, false);
+    /// assert_eq!(map.get(1, 2), Some(false));
+    /// ```
+    pub fn set(&mut self, x: i32, y: i32, val: bool) {
+        if !self.is_in_bounds(x, y) {
+            return;
+        }
+        let idx = self.get_index(x, y);
+        match val {
+            true => self.bits[idx / BITS] |= 1 << (idx % BITS),
+            false => self.bits[idx / BITS] &= !(1 << (idx % BITS)),
+        }
+    }
+
+    fn get_index(&self, x: i32, y: i32) -> usize {
+        (x + y * self.width) as usize
+    }
+
+    /// Check if the given coordinates are in bounds.
+    pub fn is_in_bounds(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && x < self.width && y < self.height
+    }
+
+    /// Count the tiles that are set to true.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut map = aoc_lib::bitmap2d::BitMap2D::new(10, 10);
+    /// map.set(0, 0, true);
+    /// map.set(9, 9, true);
+    /// map.set(9, 9, true);
+    /// assert_eq!(map.count_ones(), 2);
+    /// ```
+    pub fn count_ones(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_across_words() {
+        let mut map = BitMap2D::new(13, 11);
+        assert_eq!(map.bits.len(), 3);
+        for y in 0..map.height {
+            for x in 0..map.width {
+                map.set(x, y, (x + y) % 3 == 0);
+            }
+        }
+        for y in 0..map.height {
+            for x in 0..map.width {
+                assert_eq!(map.get(x, y), Some((x + y) % 3 == 0));
+            }
+        }
+        assert_eq!(map.count_ones(), 48);
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Represents a directed graph with weighted edges. Nodes are identified by their value.
+pub struct Graph<N> {
+    edges: HashMap<N, Vec<(N, u64)>>,
+}
+
+impl<N> Graph<N>
+where
+    N: Hash + Eq + Clone,
+{
+    /// Create a new, empty graph.
+    pub fn new() -> Graph<N> {
+        Graph {
+            edges: HashMap::new(),
+        }
+    }
+
+    /// Add a node without any edges. Adding an existing node has no effect.
+    pub fn add_node(&mut self, node: N) {
+        self.edges.entry(node).or_default();
+    }
+
+    /// Add a directed edge between two nodes. Nodes that are not part of the graph yet are added.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut graph = aoc_lib::graph::Graph::new();
+    /// graph.add_edge('a', 'b', 5);
+    /// assert_eq!(graph.node_count(), 2);
+    /// assert_eq!(graph.get_edges(&'a'), &[('b', 5)]);
+    /// assert!(graph.get_edges(&'b').is_empty());
+    /// ```
+    pub fn add_edge(&mut self, from: N, to: N, weight: u64) {
+        self.add_node(to.clone());
+        self.edges.entry(from).or_default().push((to, weight));
+    }
+
+    /// Get the outgoing edges of a node as pairs of target node and weight.
+    /// Returns an empty slice if the node is not part of the graph.
+    pub fn get_edges(&self, node: &N) -> &[(N, u64)] {
+        match self.edges.get(node) {
+            Some(edges) => edges,
+            None => &[],
+        }
+    }
+
+    /// Check whether a node is part of the graph.
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.edges.contains_key(node)
+    }
+
+    /// Get the number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl<N> Default for Graph<N>
+where
+    N: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        Graph::new()
+    }
+}
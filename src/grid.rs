@@ -0,0 +1,77 @@
+use crate::bitmap2d::BitMap2D;
+use crate::map2d::{neighbor_offsets, Map2D};
+
+/// Common access to 2D grids of tiles, so algorithms can be written once for every grid representation.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::grid::Grid;
+/// fn count_open<G: Grid<char>>(grid: &G, x: i32, y: i32) -> usize {
+///     grid.neighbors(x, y, false)
+///         .into_iter()
+///         .filter(|(nx, ny)| grid.get(*nx, *ny) == Some(&'.'))
+///         .count()
+/// }
+///
+/// let map = aoc_lib::map2d::Map2D::from_string(String::from("#.\n.."));
+/// assert_eq!(count_open(&map, 0, 0), 2);
+///
+/// fn count_set<G: Grid<bool>>(grid: &G, x: i32, y: i32) -> usize {
+///     grid.neighbors(x, y, true)
+///         .into_iter()
+///         .filter(|(nx, ny)| grid.get(*nx, *ny) == Some(&true))
+///         .count()
+/// }
+///
+/// let mut bits = aoc_lib::bitmap2d::BitMap2D::new(3, 3);
+/// bits.set(0, 0, true);
+/// bits.set(2, 1, true);
+/// assert_eq!(count_set(&bits, 1, 1), 2);
+/// assert_eq!(bits.neighbors(0, 0, false), vec![(1, 0), (0, 1)]);
+/// ```
+pub trait Grid<T> {
+    /// Get the value at the given position, or `None` if there is no tile at the position.
+    fn get(&self, x: i32, y: i32) -> Option<&T>;
+
+    /// Check if the given coordinates are in bounds.
+    fn is_in_bounds(&self, x: i32, y: i32) -> bool;
+
+    /// Get the in-bounds neighbors of a position.
+    ///
+    /// # Arguments
+    ///
+    /// `x` - x coordinate of the position
+    /// `y` - y coordinate of the position
+    /// `diagonal` - if true, diagonal neighbors are included
+    fn neighbors(&self, x: i32, y: i32, diagonal: bool) -> Vec<(i32, i32)> {
+        let offsets = neighbor_offsets(diagonal);
+        offsets
+            .iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|(nx, ny)| self.is_in_bounds(*nx, *ny))
+            .collect()
+    }
+}
+
+impl<T> Grid<T> for Map2D<T> {
+    fn get(&self, x: i32, y: i32) -> Option<&T> {
+        Map2D::get(self, x, y)
+    }
+
+    fn is_in_bounds(&self, x: i32, y: i32) -> bool {
+        Map2D::is_in_bounds(self, x, y)
+    }
+}
+
+impl Grid<bool> for BitMap2D {
+    fn get(&self, x: i32, y: i32) -> Option<&bool> {
+        BitMap2D::get(self, x, y).map(|val| match val {
+            true => &true,
+            false => &false,
+        })
+    }
+
+    fn is_in_bounds(&self, x: i32, y: i32) -> bool {
+        BitMap2D::is_in_bounds(self, x, y)
+    }
+}
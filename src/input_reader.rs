@@ -1,78 +1,418 @@
 use std::env;
+use std::fmt::Display;
 use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+use std::thread;
+use std::time::Duration;
 
 const SUBFOLDER: &str = "input";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
 const LOGIN_FAILED_RESPONSE: &str =
     "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+const HTML_PREFIX: &str = "<!DOCTYPE html";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const PREFETCH_DELAY: Duration = Duration::from_secs(1);
+
+static BASE_URL: RwLock<Option<String>> = RwLock::new(None);
+static TMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// The reasons a puzzle input can fail to be read.
+#[derive(Debug)]
+pub enum InputError {
+    /// The input has not been cached yet.
+    NotCached { path: PathBuf },
+    /// The cached input exists, but could not be read or written.
+    Io(io::Error),
+    /// The request to the aoc website did not complete within the timeout.
+    Timeout(String),
+    /// The request to the aoc website failed.
+    Request(String),
+    /// The aoc website responded with something other than a puzzle input.
+    InvalidResponse(String),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotCached { path } => {
+                write!(f, "No cached input found at {}.", path.display())
+            }
+            InputError::Io(err) => write!(f, "Failed to access cached input: {err}"),
+            InputError::Timeout(reason) | InputError::Request(reason) => write!(f, "{reason}"),
+            InputError::InvalidResponse(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
 
 /// Fetches a puzzle input from the aoc website and caches the result under the subfolder `./input` in a text file.
-/// Subsequent calls will use the cached result.
+/// Subsequent calls will use the cached result. The request times out after 30 seconds.
 ///
 /// # Arguments
 /// * `year` - year of the event, i.e. "2023"
 /// * `day` - day of the event, i.e. "24"
 /// * `path_to_cookie` - relative or absolute path to the file containing the session cookie
 pub fn get_input(year: &str, day: &str, path_to_cookie: &str) -> String {
-    let cookie = read_cookie(&path_to_cookie);
+    get_input_with_timeout(year, day, path_to_cookie, DEFAULT_TIMEOUT)
+}
+
+/// Fetches a puzzle input like [`get_input`], but with a custom timeout for the request to the aoc website.
+///
+/// # Arguments
+/// * `year` - year of the event, i.e. "2023"
+/// * `day` - day of the event, i.e. "24"
+/// * `path_to_cookie` - relative or absolute path to the file containing the session cookie
+/// * `timeout` - maximum duration of the request
+///
+/// # Panics
+/// Will panic if the request does not complete within the timeout.
+pub fn get_input_with_timeout(
+    year: &str,
+    day: &str,
+    path_to_cookie: &str,
+    timeout: Duration,
+) -> String {
+    let cookie = read_cookie(path_to_cookie);
     let input_path = get_input_path(year, day);
     match fs::read_to_string(&input_path) {
-        Err(_reason) => return fetch_input_from_site(year, day, &input_path, &cookie),
-        Ok(value) => return value,
-    };
+        Err(_reason) => match fetch_input_from_site(year, day, &input_path, &cookie, timeout) {
+            Err(InputError::Timeout(reason)) => panic!(
+                "Timed out after {} seconds while fetching puzzle input: {}",
+                timeout.as_secs_f64(),
+                reason
+            ),
+            Err(reason) => panic!("{}", reason),
+            Ok(value) => value,
+        },
+        Ok(value) => value,
+    }
+}
+
+/// Sets the host that puzzle inputs are fetched from, i.e. a mock server for testing or a mirror.
+/// Inputs are requested from `{base_url}/{year}/day/{day}/input`. The default is `https://adventofcode.com`.
+///
+/// # Arguments
+/// * `base_url` - scheme and host of the site, i.e. "http://localhost:8080"
+///
+/// # Panics
+/// Will panic if `base_url` does not start with `http://` or `https://` followed by a host, or contains whitespace.
+pub fn set_base_url(base_url: &str) {
+    let base_url = base_url.trim_end_matches('/');
+    validate_base_url(base_url).unwrap_or_else(|reason| panic!("{reason}"));
+    *BASE_URL.write().unwrap() = Some(String::from(base_url));
+}
+
+/// Fetches and caches the puzzle inputs for several days of an event, i.e. to work offline afterwards.
+/// Days that are already cached are skipped. The requests are spaced out by a short pause to respect the site.
+///
+/// # Arguments
+/// * `year` - year of the event, i.e. "2023"
+/// * `path_to_cookie` - relative or absolute path to the file containing the session cookie
+/// * `days` - days of the event to fetch, i.e. `1..=25`
+///
+/// # Returns
+/// One result per day in order, telling whether the input of that day is cached now.
+pub fn prefetch_year(
+    year: &str,
+    path_to_cookie: &str,
+    days: RangeInclusive<u32>,
+) -> Vec<Result<(), InputError>> {
+    let cookie = read_cookie(path_to_cookie);
+    let mut is_first_request = true;
+    days.map(|day| {
+        let day = day.to_string();
+        let input_path = get_input_path(year, &day);
+        if input_path.exists() {
+            return Ok(());
+        }
+        if !is_first_request {
+            thread::sleep(PREFETCH_DELAY);
+        }
+        is_first_request = false;
+        fetch_input_from_site(year, &day, &input_path, &cookie, DEFAULT_TIMEOUT).map(|_| ())
+    })
+    .collect()
+}
+
+/// Reads a puzzle input from the cache under the subfolder `./input` without ever accessing the network.
+///
+/// # Arguments
+/// * `year` - year of the event, i.e. "2023"
+/// * `day` - day of the event, i.e. "24"
+///
+/// # Errors
+/// Returns `InputError::NotCached` if the input has not been fetched yet and `InputError::Io` if reading the file fails.
+pub fn get_input_cached_only(year: &str, day: &str) -> Result<String, InputError> {
+    let input_path = get_input_path(year, day);
+    fs::read_to_string(&input_path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => InputError::NotCached { path: input_path },
+        _ => InputError::Io(err),
+    })
+}
+
+/// Fetches a puzzle input like [`get_input`] and splits it into groups of lines separated by blank lines.
+///
+/// # Arguments
+/// * `year` - year of the event, i.e. "2023"
+/// * `day` - day of the event, i.e. "24"
+/// * `path_to_cookie` - relative or absolute path to the file containing the session cookie
+pub fn get_input_groups(year: &str, day: &str, path_to_cookie: &str) -> Vec<Vec<String>> {
+    split_groups(&get_input(year, day, path_to_cookie))
+}
+
+/// Reads an example input from the subfolder `./input`. The file is expected to be named `{year}_{day}_example{n}.txt`.
+///
+/// # Arguments
+/// * `year` - year of the event, i.e. "2023"
+/// * `day` - day of the event, i.e. "24"
+/// * `n` - number of the example, i.e. 2 for an example only given in part two
+///
+/// # Returns
+/// The example input, or `None` if the file does not exist.
+pub fn get_example_n(year: &str, day: &str, n: u8) -> Option<String> {
+    fs::read_to_string(get_example_path(year, day, n)).ok()
+}
+
+fn split_groups(input: &str) -> Vec<Vec<String>> {
+    let mut groups = vec![];
+    let mut group = vec![];
+    for line in input.lines() {
+        if line.is_empty() {
+            if !group.is_empty() {
+                groups.push(group);
+                group = vec![];
+            }
+        } else {
+            group.push(String::from(line));
+        }
+    }
+    if !group.is_empty() {
+        groups.push(group);
+    }
+    groups
 }
 
 fn read_cookie(path_to_cookie: &str) -> String {
-    return fs::read_to_string(path_to_cookie).expect("Failed to read session cookie.");
+    fs::read_to_string(path_to_cookie).expect("Failed to read session cookie.")
 }
 
 fn get_input_path(year: &str, day: &str) -> PathBuf {
-    let mut path = env::current_dir().expect("Couldn't read current dir.");
-    path.push(SUBFOLDER);
     let mut yearday = String::from(year);
-    yearday.push_str("_");
+    yearday.push('_');
     yearday.push_str(day);
-    path.push(yearday);
+    get_file_path(&yearday)
+}
+
+fn get_example_path(year: &str, day: &str, n: u8) -> PathBuf {
+    get_file_path(&format!("{year}_{day}_example{n}"))
+}
+
+fn get_file_path(file_stem: &str) -> PathBuf {
+    let mut path = env::current_dir().expect("Couldn't read current dir.");
+    path.push(SUBFOLDER);
+    path.push(file_stem);
     path.set_extension("txt");
     path
 }
 
-fn fetch_input_from_site(year: &str, day: &str, input_path: &PathBuf, cookie: &str) -> String {
+fn fetch_input_from_site(
+    year: &str,
+    day: &str,
+    input_path: &Path,
+    cookie: &str,
+    timeout: Duration,
+) -> Result<String, InputError> {
     let url = build_url(year, day);
+    let (status, value) = http_get(&url, cookie, timeout)?;
+    validate_response(status, &value).map_err(InputError::InvalidResponse)?;
+    fs::create_dir_all(SUBFOLDER).map_err(InputError::Io)?;
+    write_atomically(input_path, &value).map_err(InputError::Io)?;
+    Ok(value)
+}
+
+/// Writes a file by writing to a temporary file next to it first and renaming it afterwards.
+/// Since the rename is atomic, concurrent readers never see a partially written file.
+/// Every call uses its own temporary file, so concurrent writers in any thread or process don't interfere.
+fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp_path);
+    })
+}
+
+/// Checks that a response contains a puzzle input, so error pages are never cached.
+fn validate_response(status: u16, body: &str) -> Result<(), String> {
+    if body == LOGIN_FAILED_RESPONSE {
+        return Err(String::from(
+            "Failed to fetch puzzle input. Make sure your session cookie is correct.",
+        ));
+    }
+    if status != 200 {
+        return Err(format!(
+            "Failed to fetch puzzle input. The server responded with status {status}."
+        ));
+    }
+    let prefix = body.trim_start().get(..HTML_PREFIX.len());
+    if prefix.is_some_and(|prefix| prefix.eq_ignore_ascii_case(HTML_PREFIX)) {
+        return Err(String::from(
+            "Failed to fetch puzzle input. The server responded with an HTML page.",
+        ));
+    }
+    Ok(())
+}
+
+/// Checks the shape of a base url without depending on the HTTP client.
+fn validate_base_url(base_url: &str) -> Result<(), String> {
+    let host = base_url
+        .strip_prefix("https://")
+        .or_else(|| base_url.strip_prefix("http://"));
+    match host {
+        Some(host)
+            if !host.is_empty()
+                && !host.starts_with('/')
+                && !base_url.contains(char::is_whitespace) =>
+        {
+            Ok(())
+        }
+        _ => Err(format!(
+            "Invalid base url {base_url}. Expected http:// or https:// followed by a host."
+        )),
+    }
+}
 
+/// Sends a GET request with the session cookie and returns the status code and the response body.
+/// This is the only place that depends on the HTTP client. Its errors are converted, so they don't leak into the public API.
+fn http_get(url: &str, cookie: &str, timeout: Duration) -> Result<(u16, String), InputError> {
+    let url: reqwest::Url = url
+        .parse()
+        .map_err(|err| InputError::Request(format!("Invalid url {url}: {err}")))?;
+    let to_input_error = |err: reqwest::Error| match err.is_timeout() {
+        true => InputError::Timeout(err.to_string()),
+        false => InputError::Request(err.to_string()),
+    };
     let jar = std::sync::Arc::new(reqwest::cookie::Jar::default());
     jar.add_cookie_str(cookie, &url);
     let client = reqwest::blocking::Client::builder()
         .cookie_store(true)
         .cookie_provider(std::sync::Arc::clone(&jar))
+        .timeout(timeout)
         .build()
         .unwrap();
+    let response = client.get(url).send().map_err(to_input_error)?;
+    let status = response.status().as_u16();
+    Ok((status, response.text().map_err(to_input_error)?))
+}
 
-    let response;
-    match client.get(url).send() {
-        Err(reason) => panic!("{}", reason),
-        Ok(value) => response = value.text(),
-    }
-    match response {
-        Err(reason) => panic!("{}", reason),
-        Ok(value) if value == LOGIN_FAILED_RESPONSE => {
-            panic!("Failed to fetch puzzle input. Make sure your session cookie is correct.")
-        }
-        Ok(value) => {
-            if !Path::exists(&Path::new(SUBFOLDER)) {
-                fs::create_dir(SUBFOLDER).unwrap();
-            }
-            fs::write(input_path, &value).unwrap();
-            value
-        }
+fn build_url(year: &str, day: &str) -> String {
+    match BASE_URL.read().unwrap().as_deref() {
+        Some(base_url) => build_url_with_base(base_url, year, day),
+        None => build_url_with_base(DEFAULT_BASE_URL, year, day),
     }
 }
 
-fn build_url(year: &str, day: &str) -> reqwest::Url {
-    let mut url_as_str = String::from("https://adventofcode.com/");
+fn build_url_with_base(base_url: &str, year: &str, day: &str) -> String {
+    let mut url_as_str = String::from(base_url);
+    url_as_str.push('/');
     url_as_str.push_str(year);
     url_as_str.push_str("/day/");
     url_as_str.push_str(day);
     url_as_str.push_str("/input");
-    return url_as_str.parse().unwrap();
+    url_as_str
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_example_n() {
+        assert!(get_example_path("2023", "5", 2).ends_with("input/2023_5_example2.txt"));
+        assert_eq!(get_example_n("1900", "1", 1), None);
+    }
+
+    #[test]
+    fn test_get_input_cached_only() {
+        match get_input_cached_only("1900", "1") {
+            Err(InputError::NotCached { path }) => assert!(path.ends_with("input/1900_1.txt")),
+            other => panic!("Expected a missing cache entry. Found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_write_atomically() {
+        let path = env::temp_dir().join(format!(
+            "aoc_lib_write_atomically_{}.txt",
+            std::process::id()
+        ));
+        write_atomically(&path, "first").unwrap();
+        write_atomically(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_write_atomically_concurrent() {
+        let path = env::temp_dir().join(format!(
+            "aoc_lib_write_atomically_concurrent_{}.txt",
+            std::process::id()
+        ));
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| write_atomically(&path, "input").unwrap());
+            }
+        });
+        assert_eq!(fs::read_to_string(&path).unwrap(), "input");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_split_groups() {
+        let groups = split_groups("1000\n2000\n\n3000\n\n\n4000\n5000\n");
+        assert_eq!(
+            groups,
+            vec![vec!["1000", "2000"], vec!["3000"], vec!["4000", "5000"]]
+        );
+        assert!(split_groups("").is_empty());
+    }
+
+    #[test]
+    fn test_build_url() {
+        assert_eq!(
+            build_url_with_base(DEFAULT_BASE_URL, "2023", "24"),
+            "https://adventofcode.com/2023/day/24/input"
+        );
+        assert_eq!(
+            build_url_with_base("http://localhost:8080", "2015", "1"),
+            "http://localhost:8080/2015/day/1/input"
+        );
+    }
+
+    #[test]
+    fn test_validate_base_url() {
+        assert!(validate_base_url(DEFAULT_BASE_URL).is_ok());
+        assert!(validate_base_url("http://localhost:8080").is_ok());
+        assert!(validate_base_url("adventofcode.com").is_err());
+        assert!(validate_base_url("https://").is_err());
+        assert!(validate_base_url("https://advent of code").is_err());
+    }
+
+    #[test]
+    fn test_validate_response() {
+        assert!(validate_response(200, "1\n2\n3\n").is_ok());
+        assert!(validate_response(400, LOGIN_FAILED_RESPONSE).is_err());
+        assert!(validate_response(404, "404 Not Found").is_err());
+        assert!(validate_response(200, "<!DOCTYPE html>\n<html></html>").is_err());
+        assert!(validate_response(200, "\n<!doctype html><html></html>").is_err());
+    }
 }
@@ -1,3 +1,6 @@
+pub mod bitmap2d;
+pub mod graph;
+pub mod grid;
 pub mod input_reader;
 pub mod map2d;
 pub mod tree;
@@ -1,13 +1,233 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Display;
-use std::ops::Add;
+use std::io::{self, BufRead};
+use std::ops::{Add, Mul};
+
+use crate::graph::Graph;
+use crate::util::Monoid;
+
+pub(crate) const NEIGHBORS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+pub(crate) const NEIGHBORS_DIAGONAL: [(i32, i32); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Get the offsets of the orthogonal neighbors of a tile, or of all eight neighbors if `diagonal` is true.
+pub(crate) fn neighbor_offsets(diagonal: bool) -> &'static [(i32, i32)] {
+    match diagonal {
+        true => &NEIGHBORS_DIAGONAL,
+        false => &NEIGHBORS,
+    }
+}
 
 /// Represents a contiguous set of tiles aligned in a 2D grid.
+/// The tiles are stored in row-major order, i.e. the tile at `(x, y)` is at index `y * width + x`.
 pub struct Map2D<T> {
     tiles: Vec<T>,
     width: i32,
     height: i32,
 }
 
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+const LETTERS: [(char, &str); 16] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// The reasons a map can fail to be constructed from its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Map2DError {
+    /// The input contains no rows.
+    Empty,
+    /// A row has a different length than the first one. `line` is the full text of the offending row.
+    RaggedRows {
+        row: usize,
+        expected: usize,
+        found: usize,
+        line: String,
+    },
+    /// A tile is not part of the allowed set of values.
+    InvalidTile { x: i32, y: i32, tile: char },
+    /// The number of tiles does not match the dimensions of the map.
+    SizeMismatch { expected: usize, found: usize },
+}
+
+impl Display for Map2DError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Map2DError::Empty => write!(f, "Input contains no rows."),
+            Map2DError::RaggedRows {
+                row,
+                expected,
+                found,
+                line,
+            } => write!(
+                f,
+                "Invalid length on line {row}. Expected {expected}. Found {found}. Full line: {line}"
+            ),
+            Map2DError::InvalidTile { x, y, tile } => {
+                write!(f, "Invalid tile '{tile}' at ({x}, {y}).")
+            }
+            Map2DError::SizeMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Invalid number of tiles. Expected {expected}. Found {found}."
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Map2DError {}
+
+/// A mirror line in a map, as found by `Map2D::find_reflection`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reflection {
+    /// A horizontal line below the given number of rows.
+    Horizontal(i32),
+    /// A vertical line to the right of the given number of columns.
+    Vertical(i32),
+}
+
+/// Summary of a connected region of tiles, as returned by `Map2D::region_stats`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionStat<T> {
+    /// Value of the first tile of the region in row order.
+    pub value_sample: T,
+    /// Number of tiles in the region.
+    pub area: usize,
+    /// Number of tile edges bordering the outside of the region.
+    pub perimeter: usize,
+    /// Number of straight sides of the region's outline.
+    pub sides: usize,
+}
+
+/// One of the four directions in a 2D grid. The y-axis points downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Get the change in coordinates when moving one step in this direction.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::Direction;
+    /// assert_eq!(Direction::Up.offset(), (0, -1));
+    /// assert_eq!(Direction::Right.offset(), (1, 0));
+    /// ```
+    pub fn offset(&self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+}
+
+/// One of the eight compass directions in a 2D grid, including diagonals. The y-axis points downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Get all eight directions in clockwise order, starting with `Up`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::Direction8;
+    /// assert_eq!(Direction8::all().len(), 8);
+    /// assert_eq!(Direction8::all()[1], Direction8::UpRight);
+    /// ```
+    pub fn all() -> [Direction8; 8] {
+        [
+            Direction8::Up,
+            Direction8::UpRight,
+            Direction8::Right,
+            Direction8::DownRight,
+            Direction8::Down,
+            Direction8::DownLeft,
+            Direction8::Left,
+            Direction8::UpLeft,
+        ]
+    }
+
+    /// Get the change in coordinates when moving one step in this direction.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::Direction8;
+    /// assert_eq!(Direction8::Up.offset(), (0, -1));
+    /// assert_eq!(Direction8::DownLeft.offset(), (-1, 1));
+    /// ```
+    pub fn offset(&self) -> (i32, i32) {
+        NEIGHBORS_DIAGONAL[self.index()]
+    }
+
+    /// Get the direction rotated 45 degrees clockwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::Direction8;
+    /// assert_eq!(Direction8::Up.rotate_45_cw(), Direction8::UpRight);
+    /// assert_eq!(Direction8::UpLeft.rotate_45_cw(), Direction8::Up);
+    /// ```
+    pub fn rotate_45_cw(&self) -> Direction8 {
+        Direction8::all()[(self.index() + 1) % 8]
+    }
+
+    /// Get the direction rotated 45 degrees counterclockwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::Direction8;
+    /// assert_eq!(Direction8::Up.rotate_45_ccw(), Direction8::UpLeft);
+    /// assert_eq!(Direction8::Right.rotate_45_ccw(), Direction8::UpRight);
+    /// ```
+    pub fn rotate_45_ccw(&self) -> Direction8 {
+        Direction8::all()[(self.index() + 7) % 8]
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
 impl<T> Map2D<T> {
     /// Create a new map with all tiles initialized with the given value.
     ///
@@ -27,6 +247,145 @@ impl<T> Map2D<T> {
         }
     }
 
+    /// Create a new map out of a buffer of tiles in row-major order, as returned by `as_slice`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Map2DError::SizeMismatch` if the length of the buffer is not `width * height`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::{Map2D, Map2DError};
+    /// let map = Map2D::from_slice(3, 2, &[1, 2, 3, 4, 5, 6]).unwrap();
+    /// assert_eq!(map.get(0, 1), Some(&4));
+    /// assert_eq!(map.as_slice(), &[1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(
+    ///     Map2D::from_slice(2, 2, &[1, 2, 3]).err(),
+    ///     Some(Map2DError::SizeMismatch { expected: 4, found: 3 })
+    /// );
+    /// ```
+    pub fn from_slice(width: i32, height: i32, data: &[T]) -> Result<Map2D<T>, Map2DError>
+    where
+        T: Clone,
+    {
+        let expected = width.max(0) as usize * height.max(0) as usize;
+        if data.len() != expected {
+            return Err(Map2DError::SizeMismatch {
+                expected,
+                found: data.len(),
+            });
+        }
+        Ok(Map2D {
+            tiles: data.to_vec(),
+            width,
+            height,
+        })
+    }
+
+    /// Create a new map out of an input string, splitting each line into tiles with a tokenizer.
+    /// If the input string contains any empty lines, the remainder of the input will be ignored.
+    ///
+    /// # Arguments
+    ///
+    /// `tokenizer` - a closure splitting a line into the values of its tiles
+    ///
+    /// # Errors
+    ///
+    /// Returns `Map2DError::Empty` if the input contains no lines
+    /// and `Map2DError::RaggedRows` if any line has a different number of tiles than the first one.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::{Map2D, Map2DError};
+    /// let tokenizer = |line: &str| line.split_whitespace().map(|n| n.parse().unwrap()).collect();
+    /// let map: Map2D<u32> = Map2D::from_string_tokens(String::from("1 22 3\n4 5 66"), tokenizer).unwrap();
+    /// assert_eq!(map.width(), 3);
+    /// assert_eq!(map.get(1, 0), Some(&22));
+    /// assert_eq!(map.get(2, 1), Some(&66));
+    ///
+    /// assert_eq!(
+    ///     Map2D::from_string_tokens(String::from("1 2\n3"), tokenizer).err(),
+    ///     Some(Map2DError::RaggedRows { row: 1, expected: 2, found: 1, line: String::from("3") })
+    /// );
+    /// ```
+    pub fn from_string_tokens<F>(input: String, tokenizer: F) -> Result<Map2D<T>, Map2DError>
+    where
+        F: Fn(&str) -> Vec<T>,
+    {
+        let lines: Vec<&str> = input_lines(&input).collect();
+        let rows: Vec<Vec<T>> = lines.iter().copied().map(tokenizer).collect();
+        let width = match rows.first() {
+            Some(row) => row.len(),
+            None => return Err(Map2DError::Empty),
+        };
+        for (idx, row) in rows.iter().enumerate() {
+            if row.len() != width {
+                return Err(Map2DError::RaggedRows {
+                    row: idx,
+                    expected: width,
+                    found: row.len(),
+                    line: String::from(lines[idx]),
+                });
+            }
+        }
+        let height = rows.len();
+        Ok(Map2D {
+            tiles: rows.into_iter().flatten().collect(),
+            width: width as i32,
+            height: height as i32,
+        })
+    }
+
+    /// Create a new map filled with values sampled from weighted choices, using a seeded random number generator.
+    /// The same seed always produces the same map. Only available with the `rand` feature.
+    ///
+    /// # Arguments
+    ///
+    /// `choices` - the possible values of a tile and their relative weights
+    /// `seed` - seed of the random number generator
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the weights of all choices add up to 0.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::random(20, 10, &[('#', 1), ('.', 3)], 42);
+    /// let walls = map.count_range(0, 19, 0, 9, |val| *val == '#');
+    /// assert!(walls > 20 && walls < 80);
+    ///
+    /// let other = aoc_lib::map2d::Map2D::random(20, 10, &[('#', 1), ('.', 3)], 42);
+    /// assert!(map.diff(&other).is_empty());
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn random(width: i32, height: i32, choices: &[(T, u32)], seed: u64) -> Map2D<T>
+    where
+        T: Clone,
+    {
+        let total: u64 = choices.iter().map(|(_, weight)| *weight as u64).sum();
+        if total == 0 {
+            panic!("The weights of the choices add up to 0.");
+        }
+        let mut state = seed;
+        let tiles = (0..width * height)
+            .map(|_| {
+                let mut roll = random_below(&mut state, total);
+                for (val, weight) in choices {
+                    if roll < *weight as u64 {
+                        return val.clone();
+                    }
+                    roll -= *weight as u64;
+                }
+                unreachable!()
+            })
+            .collect();
+        Map2D {
+            tiles,
+            width,
+            height,
+        }
+    }
+
     /// Get the character at the given position.
     ///
     /// # Examples
@@ -39,7 +398,7 @@ impl<T> Map2D<T> {
     /// ```
     pub fn get(&self, x: i32, y: i32) -> Option<&T> {
         match self.is_in_bounds(x, y) {
-            true => Some(&self.tiles[self.get_index(x as i32, y as i32)]),
+            true => Some(&self.tiles[self.get_index(x, y)]),
             false => None,
         }
     }
@@ -57,7 +416,7 @@ impl<T> Map2D<T> {
         if !self.is_in_bounds(x, y) {
             return;
         }
-        let idx = self.get_index(x as i32, y as i32);
+        let idx = self.get_index(x, y);
         self.tiles[idx] = val;
     }
 
@@ -65,6 +424,40 @@ impl<T> Map2D<T> {
         (x + y * self.width) as usize
     }
 
+    fn get_coords(&self, idx: usize) -> (i32, i32) {
+        (idx as i32 % self.width, idx as i32 / self.width)
+    }
+
+    /// Get the value at the given index of the underlying storage. Tiles are stored row by row.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("12345\n67890");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.get_index_val(6), Some(&'7'));
+    /// assert_eq!(map.get_index_val(10), None);
+    /// ```
+    pub fn get_index_val(&self, idx: usize) -> Option<&T> {
+        self.tiles.get(idx)
+    }
+
+    /// Set the value at the given index of the underlying storage. Tiles are stored row by row.
+    /// Indices out of bounds will be ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("12345\n67890");
+    /// let mut map = aoc_lib::map2d::Map2D::from_string(input);
+    /// map.set_index(9, 'A');
+    /// assert_eq!(Some(&'A'), map.get(4, 1));
+    /// map.set_index(10, 'A');
+    /// ```
+    pub fn set_index(&mut self, idx: usize, val: T) {
+        if let Some(tile) = self.tiles.get_mut(idx) {
+            *tile = val;
+        }
+    }
+
     /// Check if the given coordinates are in bounds.
     ///
     /// # Examples
@@ -77,7 +470,7 @@ impl<T> Map2D<T> {
     /// assert_eq!(map.is_in_bounds(0, 0), true);
     /// ```
     pub fn is_in_bounds(&self, x: i32, y: i32) -> bool {
-        x >= 0 && y >= 0 && (x as i32) < self.width && (y as i32) < self.height
+        x >= 0 && y >= 0 && x < self.width && y < self.height
     }
 
     /// Prints the map to the console.
@@ -162,83 +555,2085 @@ impl<T> Map2D<T> {
         value.unwrap()
     }
 
-    pub fn width(&self) -> i32 {
-        self.width
+    /// Aggregates values in the map into a single value, combining them with the `Monoid` implementation of the result type.
+    /// Unlike `aggregate`, this can collect into types like `Vec` or `String` and returns the empty value for an empty map.
+    ///
+    /// # Arguments
+    ///
+    /// `f` - a closure returning the value that should be aggregated
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("ab\ncd");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.aggregate_monoid(|val, _, _| val.to_string()), "abcd");
+    /// assert_eq!(map.aggregate_monoid(|val, x, y| if *val == 'c' { vec![(x, y)] } else { vec![] }), vec![(0, 1)]);
+    /// assert_eq!(map.aggregate_monoid(|_, x, _| x), 2);
+    /// ```
+    pub fn aggregate_monoid<F, R>(&self, f: F) -> R
+    where
+        R: Monoid,
+        F: Fn(&T, i32, i32) -> R,
+    {
+        self.tiles
+            .iter()
+            .enumerate()
+            .fold(R::empty(), |acc, (idx, val)| {
+                let idx = idx as i32;
+                acc.combine(f(val, idx % self.width, idx / self.width))
+            })
     }
 
-    pub fn height(&self) -> i32 {
-        self.height
+    /// Find the tile with the largest key. If several tiles share the largest key, the first one in row order is returned.
+    ///
+    /// # Returns
+    /// The coordinates and value of the tile, or `None` if the map is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("303\n255\n653");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.max_by_key(|val| *val), Some(((0, 2), &'6')));
+    /// assert_eq!(map.max_by_key(|val| *val == '5'), Some(((1, 1), &'5')));
+    /// ```
+    pub fn max_by_key<K, F>(&self, f: F) -> Option<((i32, i32), &T)>
+    where
+        K: Ord,
+        F: Fn(&T) -> K,
+    {
+        let idx = (0..self.tiles.len())
+            .max_by(|a, b| f(&self.tiles[*a]).cmp(&f(&self.tiles[*b])).then(b.cmp(a)))?;
+        Some((self.get_coords(idx), &self.tiles[idx]))
     }
-}
 
-impl Map2D<char> {
-    /// Create a new map out of an input string. If the input string contains any empty lines, the remainder of the input will be ignored.
-    ///
-    /// # Panics
+    /// Find the tile with the smallest key. If several tiles share the smallest key, the first one in row order is returned.
     ///
-    /// Will panic if any line in the string has a different length than the first one.
+    /// # Returns
+    /// The coordinates and value of the tile, or `None` if the map is empty.
     ///
     /// # Examples
     /// ```
-    /// let input = String::from("12345\n12345");
+    /// let input = String::from("303\n255\n653");
     /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.min_by_key(|val| *val), Some(((1, 0), &'0')));
+    ///
+    /// let map = aoc_lib::map2d::Map2D::<char>::new(0, 0, '.');
+    /// assert_eq!(map.min_by_key(|val| *val), None);
     /// ```
-    pub fn from_string(input: String) -> Map2D<char> {
-        let split: Vec<&str> = input
-            .split("\n")
-            .take_while(|line| !line.is_empty())
-            .collect();
-        let width = split[0].len();
-        for (idx, line) in split.iter().enumerate() {
-            if line.len() != width {
-                panic!(
-                    "Invalid length on line {idx}. Expected {width}. Found {}. Full line: {line}",
-                    line.len()
-                );
-            }
-        }
-        let height = split.len();
-        Map2D {
-            tiles: split.join("").chars().collect(),
-            width: width as i32,
-            height: height as i32,
-        }
+    pub fn min_by_key<K, F>(&self, f: F) -> Option<((i32, i32), &T)>
+    where
+        K: Ord,
+        F: Fn(&T) -> K,
+    {
+        let idx = (0..self.tiles.len()).min_by_key(|idx| f(&self.tiles[*idx]))?;
+        Some((self.get_coords(idx), &self.tiles[idx]))
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    #[should_panic]
-    fn test_from_string_panics() {
-        let input = String::from("123456\n12345");
-        Map2D::from_string(input);
+    /// Aggregates values in the given rectangle, multiplying each value with a weight derived from its coordinates.
+    /// Coordinates out of bounds will be skipped, like in `aggregate_range`.
+    /// Dividing the weighted sum by the total weight gives the weighted mean of the values.
+    ///
+    /// # Arguments
+    ///
+    /// `x0` - lower bound for the width
+    /// `x1` - inclusive upper bound for the width
+    /// `y0` - lower bound for the height
+    /// `y1` - inclusive upper bound for the height
+    /// `f` - a closure returning the value of a tile
+    /// `weight` - a closure returning the weight of a position
+    ///
+    /// # Returns
+    /// The weighted sum of the values and the sum of the weights. Both are empty if the rectangle contains no tiles.
+    ///
+    /// # Examples
+    /// ```
+    /// let tokenizer = |line: &str| line.split(' ').map(|n| n.parse().unwrap()).collect();
+    /// let map = aoc_lib::map2d::Map2D::<f64>::from_string_tokens(String::from("1 2 3\n4 5 6"), tokenizer).unwrap();
+    /// assert_eq!(map.weighted_aggregate_range(0, 2, 0, 1, |val| *val, |x, _| x as f64), (25.0, 6.0));
+    /// assert_eq!(map.weighted_aggregate_range(0, 2, 1, 1, |val| *val, |_, _| 1.0), (15.0, 3.0));
+    /// assert_eq!(map.weighted_aggregate_range(5, 9, 0, 1, |val| *val, |_, _| 1.0), (0.0, 0.0));
+    /// ```
+    pub fn weighted_aggregate_range<F, W, R>(
+        &self,
+        x0: i32,
+        x1: i32,
+        y0: i32,
+        y1: i32,
+        f: F,
+        weight: W,
+    ) -> (R, R)
+    where
+        R: Monoid + Mul<Output = R> + Copy,
+        F: Fn(&T) -> R,
+        W: Fn(i32, i32) -> R,
+    {
+        self.coords_in(x0, x1, y0, y1)
+            .fold((R::empty(), R::empty()), |(sum, total), (x, y)| {
+                let w = weight(x, y);
+                let val = f(&self.tiles[self.get_index(x, y)]);
+                (sum.combine(val * w), total.combine(w))
+            })
     }
 
-    #[test]
-    fn test_from_string() {
-        let input = String::from("12345\n12345\n12345");
-        let map = Map2D::from_string(input);
-        assert_eq!(map.width, 5);
-        assert_eq!(map.height, 3);
+    /// Count the tiles in the given rectangle that satisfy a predicate.
+    /// Coordinates out of bounds will be skipped, like in `aggregate_range`.
+    ///
+    /// # Arguments
+    ///
+    /// `x0` - lower bound for the width
+    /// `x1` - inclusive upper bound for the width
+    /// `y0` - lower bound for the height
+    /// `y1` - inclusive upper bound for the height
+    /// `f` - a closure returning true if the tile should be counted
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("#..#\n.##.\n#..#");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.count_range(0, 1, 0, 1, |val| *val == '#'), 2);
+    /// assert_eq!(map.count_range(-5, 10, -5, 10, |val| *val == '#'), 6);
+    /// assert_eq!(map.count_range(5, 10, 0, 2, |val| *val == '#'), 0);
+    /// ```
+    pub fn count_range<F>(&self, x0: i32, x1: i32, y0: i32, y1: i32, f: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.coords_in(x0, x1, y0, y1)
+            .filter(|(x, y)| f(&self.tiles[self.get_index(*x, *y)]))
+            .count()
+    }
 
-        let input = String::from("12345\n12345\n12345\n");
-        let map = Map2D::from_string(input);
-        assert_eq!(map.width, 5);
-        assert_eq!(map.height, 3);
+    /// Compute the weighted sum of the 3x3 neighborhood of every tile.
+    /// The kernel is indexed as `kernel[row][column]`, so `kernel[0][0]` weighs the upper left neighbor
+    /// and `kernel[1][1]` weighs the tile itself. The kernel is applied as given, without flipping.
+    ///
+    /// # Arguments
+    ///
+    /// `kernel` - weights of the neighborhood
+    /// `border` - value used for neighbors out of bounds
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::from_points(&[(0, 0), (1, 1), (2, 2)]);
+    /// let neighbors = map.convolve(&[[1, 1, 1], [1, 0, 1], [1, 1, 1]], false);
+    /// assert_eq!(neighbors.get(1, 1), Some(&2));
+    /// assert_eq!(neighbors.get(2, 0), Some(&1));
+    ///
+    /// let above = map.convolve(&[[0, 1, 0], [0, 0, 0], [0, 0, 0]], true);
+    /// assert_eq!(above.get(1, 2), Some(&1));
+    /// assert_eq!(above.get(0, 1), Some(&1));
+    /// assert_eq!(above.get(1, 0), Some(&1));
+    /// assert_eq!(above.get(2, 1), Some(&0));
+    /// ```
+    pub fn convolve(&self, kernel: &[[i32; 3]; 3], border: T) -> Map2D<i32>
+    where
+        T: Into<i32> + Copy,
+    {
+        let mut result = Map2D::new(self.width, self.height, 0);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let mut sum = 0;
+                for (ky, row) in kernel.iter().enumerate() {
+                    for (kx, weight) in row.iter().enumerate() {
+                        let val = *self
+                            .get(x + kx as i32 - 1, y + ky as i32 - 1)
+                            .unwrap_or(&border);
+                        sum += weight * val.into();
+                    }
+                }
+                result.set(x, y, sum);
+            }
+        }
+        result
+    }
 
-        let input = String::from("12345\n12345\n12345\n\n12345");
-        let map = Map2D::from_string(input);
-        assert_eq!(map.width, 5);
-        assert_eq!(map.height, 3);
+    /// Sums all tiles using checked arithmetic.
+    ///
+    /// # Returns
+    /// The sum of all tiles, or `None` if the sum overflows an `i64`.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::<i32>::new(10, 10, i32::MAX);
+    /// assert_eq!(map.try_aggregate_sum(), Some(100 * i32::MAX as i64));
+    ///
+    /// let map = aoc_lib::map2d::Map2D::<i64>::new(2, 1, i64::MAX);
+    /// assert_eq!(map.try_aggregate_sum(), None);
+    /// ```
+    pub fn try_aggregate_sum(&self) -> Option<i64>
+    where
+        T: Into<i64> + Copy,
+    {
+        self.tiles
+            .iter()
+            .try_fold(0i64, |sum, val| sum.checked_add((*val).into()))
     }
 
-    #[test]
-    fn test_print() {
+    /// Get an iterator over the in-bounds coordinates of the given rectangle, ordered row by row.
+    /// Coordinates out of bounds will be skipped, like in `aggregate_range`.
+    ///
+    /// # Arguments
+    ///
+    /// `x0` - lower bound for the width
+    /// `x1` - inclusive upper bound for the width
+    /// `y0` - lower bound for the height
+    /// `y1` - inclusive upper bound for the height
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::<i32>::new(10, 10, 1);
+    /// let coords: Vec<(i32, i32)> = map.coords_in(8, 12, -1, 0).collect();
+    /// assert_eq!(coords, vec![(8, 0), (9, 0)]);
+    /// assert_eq!(map.coords_in(0, 4, 0, 4).count(), 25);
+    /// ```
+    pub fn coords_in(
+        &self,
+        x0: i32,
+        x1: i32,
+        y0: i32,
+        y1: i32,
+    ) -> impl Iterator<Item = (i32, i32)> {
+        let x0 = x0.max(0);
+        let x1 = x1.min(self.width - 1);
+        let y0 = y0.max(0);
+        let y1 = y1.min(self.height - 1);
+        (y0..=y1).flat_map(move |y| (x0..=x1).map(move |x| (x, y)))
+    }
+
+    /// Get the values of all tiles as a slice in row-major order, i.e. the tile at `(x, y)` is at index `y * width + x`.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::from_string(String::from("ab\ncd"));
+    /// assert_eq!(map.as_slice(), &['a', 'b', 'c', 'd']);
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        &self.tiles
+    }
+
+    /// Get an iterator over the coordinates of all tiles and a mutable reference to their values, ordered row by row.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut map = aoc_lib::map2d::Map2D::<char>::new(3, 3, '.');
+    /// for (x, y, tile) in map.iter_mut() {
+    ///     if x == y {
+    ///         *tile = 'X';
+    ///     }
+    /// }
+    /// assert_eq!(map.get(1, 1), Some(&'X'));
+    /// assert_eq!(map.get(1, 0), Some(&'.'));
+    /// ```
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (i32, i32, &mut T)> {
+        let width = self.width;
+        self.tiles
+            .iter_mut()
+            .enumerate()
+            .map(move |(idx, val)| (idx as i32 % width, idx as i32 / width, val))
+    }
+
+    /// Sums a value computed for every tile into a fingerprint of the map. Unlike `aggregate`, this returns 0 for an empty map.
+    ///
+    /// # Arguments
+    ///
+    /// `f` - a closure returning the contribution of a tile to the checksum
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("O.\n.O\nO.");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// let load = map.checksum(|val, _, y| if *val == 'O' { (map.height() - y) as u64 } else { 0 });
+    /// assert_eq!(load, 6);
+    ///
+    /// let map = aoc_lib::map2d::Map2D::<char>::new(0, 0, '.');
+    /// assert_eq!(map.checksum(|_, _, _| 1), 0);
+    /// ```
+    pub fn checksum<F>(&self, f: F) -> u64
+    where
+        F: Fn(&T, i32, i32) -> u64,
+    {
+        self.tiles
+            .iter()
+            .enumerate()
+            .map(|(idx, val)| {
+                let idx = idx as i32;
+                f(val, idx % self.width, idx / self.width)
+            })
+            .sum()
+    }
+
+    /// Transforms every tile in place. Tiles are updated one after another row by row,
+    /// so closures reading neighboring tiles through the map would observe a mix of old and new values.
+    ///
+    /// # Arguments
+    ///
+    /// `f` - a closure receiving the current value and coordinates of a tile, returning the new value
+    ///
+    /// # Examples
+    /// ```
+    /// let mut map = aoc_lib::map2d::Map2D::<i32>::new(3, 2, 1);
+    /// map.apply(|val, x, y| val + x * y);
+    /// assert_eq!(map.get(0, 0), Some(&1));
+    /// assert_eq!(map.get(2, 1), Some(&3));
+    /// ```
+    pub fn apply<F>(&mut self, f: F)
+    where
+        F: Fn(&T, i32, i32) -> T,
+    {
+        let width = self.width;
+        for (idx, tile) in self.tiles.iter_mut().enumerate() {
+            let idx = idx as i32;
+            *tile = f(tile, idx % width, idx / width);
+        }
+    }
+
+    /// Count the edges of a region that border a tile outside of the region or the boundary of the map.
+    ///
+    /// # Arguments
+    ///
+    /// `region` - coordinates of the tiles belonging to the region
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("AAB\nABB\nCCC");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.perimeter(&[(0, 0)]), 4);
+    /// assert_eq!(map.perimeter(&[(0, 0), (1, 0), (0, 1)]), 8);
+    /// assert_eq!(map.perimeter(&[(0, 2), (1, 2), (2, 2)]), 8);
+    /// ```
+    pub fn perimeter(&self, region: &[(i32, i32)]) -> usize {
+        let tiles: HashSet<&(i32, i32)> = region.iter().collect();
+        tiles
+            .iter()
+            .map(|(x, y)| {
+                NEIGHBORS
+                    .iter()
+                    .filter(|(dx, dy)| !tiles.contains(&(x + dx, y + dy)))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Count the convex and concave corners of a region's outline. This equals the number of straight sides of the region.
+    ///
+    /// # Arguments
+    ///
+    /// `region` - coordinates of the tiles belonging to the region
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("AAB\nABB\nCCC");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.count_corners(&[(0, 0)]), 4);
+    /// assert_eq!(map.count_corners(&[(0, 0), (1, 0), (0, 1)]), 6);
+    /// assert_eq!(map.count_corners(&[(0, 2), (1, 2), (2, 2)]), 4);
+    /// ```
+    pub fn count_corners(&self, region: &[(i32, i32)]) -> usize {
+        let tiles: HashSet<&(i32, i32)> = region.iter().collect();
+        tiles
+            .iter()
+            .map(|(x, y)| {
+                [(1, -1), (1, 1), (-1, 1), (-1, -1)]
+                    .iter()
+                    .filter(|(dx, dy)| {
+                        let horizontal = tiles.contains(&(x + dx, *y));
+                        let vertical = tiles.contains(&(*x, y + dy));
+                        let diagonal = tiles.contains(&(x + dx, y + dy));
+                        (!horizontal && !vertical) || (horizontal && vertical && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Get the tiles of a region that have at least one orthogonal neighbor outside of the region,
+    /// including the boundary of the map.
+    ///
+    /// # Arguments
+    ///
+    /// `region` - coordinates of the tiles belonging to the region
+    ///
+    /// # Returns
+    /// The outline of the region, in the order the tiles appear in `region`.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::<char>::new(3, 3, 'A');
+    /// let region: Vec<(i32, i32)> = map.coords_in(0, 2, 0, 2).collect();
+    /// let outline = map.boundary_of(&region);
+    /// assert_eq!(outline.len(), 8);
+    /// assert!(!outline.contains(&(1, 1)));
+    /// assert_eq!(map.boundary_of(&[(1, 1)]), vec![(1, 1)]);
+    /// ```
+    pub fn boundary_of(&self, region: &[(i32, i32)]) -> Vec<(i32, i32)> {
+        let tiles: HashSet<&(i32, i32)> = region.iter().collect();
+        region
+            .iter()
+            .filter(|(x, y)| {
+                NEIGHBORS
+                    .iter()
+                    .any(|(dx, dy)| !tiles.contains(&(x + dx, y + dy)))
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Split the map into regions of orthogonally connected tiles and summarize each of them,
+    /// combining the results of `perimeter` and `count_corners`.
+    ///
+    /// # Arguments
+    ///
+    /// `connected` - a closure receiving the values of two neighboring tiles, returning true if they belong to the same region
+    ///
+    /// # Returns
+    /// One entry per region, ordered by the first tile of each region in row order.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("AAB\nABB\nCCC");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// let stats = map.region_stats(|a, b| a == b);
+    /// assert_eq!(stats.len(), 3);
+    /// assert_eq!(stats[0].value_sample, 'A');
+    /// assert_eq!((stats[0].area, stats[0].perimeter, stats[0].sides), (3, 8, 6));
+    /// assert_eq!((stats[2].area, stats[2].perimeter, stats[2].sides), (3, 8, 4));
+    /// assert_eq!(stats.iter().map(|s| s.area * s.perimeter).sum::<usize>(), 72);
+    /// ```
+    pub fn region_stats<F>(&self, connected: F) -> Vec<RegionStat<T>>
+    where
+        T: Clone,
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut visited = vec![false; self.tiles.len()];
+        let mut stats = vec![];
+        for start_idx in 0..self.tiles.len() {
+            if visited[start_idx] {
+                continue;
+            }
+            visited[start_idx] = true;
+            let mut region = vec![];
+            let mut stack = vec![self.get_coords(start_idx)];
+            while let Some((x, y)) = stack.pop() {
+                region.push((x, y));
+                let val = &self.tiles[self.get_index(x, y)];
+                for (dx, dy) in NEIGHBORS {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if !self.is_in_bounds(nx, ny) {
+                        continue;
+                    }
+                    let idx = self.get_index(nx, ny);
+                    if !visited[idx] && connected(val, &self.tiles[idx]) {
+                        visited[idx] = true;
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+            stats.push(RegionStat {
+                value_sample: self.tiles[start_idx].clone(),
+                area: region.len(),
+                perimeter: self.perimeter(&region),
+                sides: self.count_corners(&region),
+            });
+        }
+        stats
+    }
+
+    /// Create a new map by repeating this map `nx` times horizontally and `ny` times vertically.
+    ///
+    /// # Arguments
+    ///
+    /// `nx` - number of repetitions along the width
+    /// `ny` - number of repetitions along the height
+    /// `transform` - a closure receiving the original value and the offsets of the repetition, returning the new value
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::<u32>::new(2, 1, 8);
+    /// let tiled = map.tile(3, 2, |val, tx, ty| (val + tx as u32 + ty as u32 - 1) % 9 + 1);
+    /// assert_eq!(tiled.width(), 6);
+    /// assert_eq!(tiled.height(), 2);
+    /// assert_eq!(tiled.get(1, 0), Some(&8));
+    /// assert_eq!(tiled.get(2, 0), Some(&9));
+    /// assert_eq!(tiled.get(4, 0), Some(&1));
+    /// assert_eq!(tiled.get(5, 1), Some(&2));
+    /// ```
+    pub fn tile<F>(&self, nx: i32, ny: i32, transform: F) -> Map2D<T>
+    where
+        F: Fn(&T, i32, i32) -> T,
+    {
+        let width = self.width * nx;
+        let height = self.height * ny;
+        let mut tiles = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let val = &self.tiles[self.get_index(x % self.width, y % self.height)];
+                tiles.push(transform(val, x / self.width, y / self.height));
+            }
+        }
+        Map2D {
+            tiles,
+            width,
+            height,
+        }
+    }
+
+    /// Get an iterator over all coordinates on the outer border of the map. Each coordinate is returned exactly once.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::<char>::new(4, 3, '.');
+    /// let border: Vec<(i32, i32)> = map.border_coords().collect();
+    /// assert_eq!(border.len(), 10);
+    /// assert!(border.contains(&(0, 0)));
+    /// assert!(border.contains(&(3, 2)));
+    /// assert!(!border.contains(&(1, 1)));
+    ///
+    /// let map = aoc_lib::map2d::Map2D::<char>::new(3, 1, '.');
+    /// assert_eq!(map.border_coords().collect::<Vec<_>>(), vec![(0, 0), (1, 0), (2, 0)]);
+    ///
+    /// assert_eq!(aoc_lib::map2d::Map2D::<char>::new(3, 0, '.').border_coords().count(), 0);
+    /// assert_eq!(aoc_lib::map2d::Map2D::<char>::new(0, 3, '.').border_coords().count(), 0);
+    /// ```
+    pub fn border_coords(&self) -> impl Iterator<Item = (i32, i32)> {
+        // A map without rows or columns has no tiles, so it has no border either.
+        let (width, height) = match self.width == 0 || self.height == 0 {
+            true => (0, 0),
+            false => (self.width, self.height),
+        };
+        let top = (0..width).map(|x| (x, 0));
+        let sides = (1..height - 1).flat_map(move |y| {
+            let right = if width > 1 {
+                Some((width - 1, y))
+            } else {
+                None
+            };
+            std::iter::once((0, y)).chain(right)
+        });
+        let bottom = (0..width)
+            .filter(move |_| height > 1)
+            .map(move |x| (x, height - 1));
+        top.chain(sides).chain(bottom)
+    }
+
+    /// Get the values on the outer border of the map in clockwise order, starting with the upper left tile.
+    /// The top row is traversed left to right, the right column top to bottom, the bottom row right to left
+    /// and the left column bottom to top. Each corner is returned exactly once.
+    /// A map with a single row or column returns its tiles left to right or top to bottom.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("123\n4.5\n678");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// let border: String = map.border_values().into_iter().collect();
+    /// assert_eq!(border, "12358764");
+    ///
+    /// let map = aoc_lib::map2d::Map2D::from_string(String::from("1\n2\n3"));
+    /// assert_eq!(map.border_values(), vec![&'1', &'2', &'3']);
+    /// ```
+    pub fn border_values(&self) -> Vec<&T> {
+        if self.width == 0 || self.height == 0 {
+            return vec![];
+        }
+        let (right, bottom) = (self.width - 1, self.height - 1);
+        let mut coords: Vec<(i32, i32)> = (0..=right).map(|x| (x, 0)).collect();
+        coords.extend((1..=bottom).map(|y| (right, y)));
+        if bottom > 0 && right > 0 {
+            coords.extend((0..right).rev().map(|x| (x, bottom)));
+            coords.extend((1..bottom).rev().map(|y| (0, y)));
+        }
+        coords
+            .into_iter()
+            .map(|(x, y)| &self.tiles[self.get_index(x, y)])
+            .collect()
+    }
+
+    /// Exchange two rows of the map. Nothing happens if either row is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("123\n456\n789");
+    /// let mut map = aoc_lib::map2d::Map2D::from_string(input);
+    /// map.swap_rows(0, 2);
+    /// assert_eq!(map.get(0, 0), Some(&'7'));
+    /// assert_eq!(map.get(2, 2), Some(&'3'));
+    /// map.swap_rows(0, 3);
+    /// assert_eq!(map.get(0, 0), Some(&'7'));
+    /// ```
+    pub fn swap_rows(&mut self, a: i32, b: i32) {
+        if a == b || !self.is_in_bounds(0, a) || !self.is_in_bounds(0, b) {
+            return;
+        }
+        let width = self.width as usize;
+        let (first, second) = (a.min(b) as usize * width, a.max(b) as usize * width);
+        let (head, tail) = self.tiles.split_at_mut(second);
+        head[first..first + width].swap_with_slice(&mut tail[..width]);
+    }
+
+    /// Exchange two columns of the map. Nothing happens if either column is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("123\n456\n789");
+    /// let mut map = aoc_lib::map2d::Map2D::from_string(input);
+    /// map.swap_columns(0, 1);
+    /// assert_eq!(map.get(0, 0), Some(&'2'));
+    /// assert_eq!(map.get(1, 2), Some(&'7'));
+    /// map.swap_columns(-1, 1);
+    /// assert_eq!(map.get(1, 2), Some(&'7'));
+    /// ```
+    pub fn swap_columns(&mut self, a: i32, b: i32) {
+        if !self.is_in_bounds(a, 0) || !self.is_in_bounds(b, 0) {
+            return;
+        }
+        for y in 0..self.height {
+            let (idx_a, idx_b) = (self.get_index(a, y), self.get_index(b, y));
+            self.tiles.swap(idx_a, idx_b);
+        }
+    }
+
+    /// Replace every tile matching a value with another value.
+    ///
+    /// # Returns
+    /// The number of replaced tiles.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("#.#\n..#");
+    /// let mut map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.replace(&'#', '.'), 3);
+    /// assert_eq!(map.get(0, 0), Some(&'.'));
+    /// assert_eq!(map.replace(&'#', '.'), 0);
+    /// ```
+    pub fn replace(&mut self, from: &T, to: T) -> usize
+    where
+        T: PartialEq + Clone,
+    {
+        let mut count = 0;
+        for tile in self.tiles.iter_mut().filter(|tile| *tile == from) {
+            *tile = to.clone();
+            count += 1;
+        }
+        count
+    }
+
+    /// Slide all movable tiles as far as possible in the given direction.
+    /// Tiles stop when they hit a blocker, another movable tile or the edge of the map.
+    /// Tiles that are neither movable nor blockers are treated as empty space and swap places with the moving tiles.
+    ///
+    /// # Arguments
+    ///
+    /// `dir` - direction the tiles should move in
+    /// `movable` - a closure returning true if the tile can move
+    /// `blocker` - a closure returning true if the tile blocks movement
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::{Direction, Map2D};
+    /// let input = String::from("O.O\n.#.\nOO.");
+    /// let mut map = Map2D::from_string(input);
+    /// map.roll(Direction::Up, |val| *val == 'O', |val| *val == '#');
+    /// assert_eq!(map.get(0, 0), Some(&'O'));
+    /// assert_eq!(map.get(0, 1), Some(&'O'));
+    /// assert_eq!(map.get(1, 2), Some(&'O'));
+    /// assert_eq!(map.get(2, 0), Some(&'O'));
+    /// map.roll(Direction::Right, |val| *val == 'O', |val| *val == '#');
+    /// assert_eq!(map.get(1, 0), Some(&'O'));
+    /// assert_eq!(map.get(2, 0), Some(&'O'));
+    /// assert_eq!(map.get(0, 1), Some(&'O'));
+    /// assert_eq!(map.get(2, 2), Some(&'O'));
+    /// ```
+    pub fn roll<M, B>(&mut self, dir: Direction, movable: M, blocker: B)
+    where
+        M: Fn(&T) -> bool,
+        B: Fn(&T) -> bool,
+    {
+        let (width, height) = (self.width, self.height);
+        let (line_count, line_length) = match dir {
+            Direction::Up | Direction::Down => (width, height),
+            Direction::Left | Direction::Right => (height, width),
+        };
+        // pos 0 is the tile at the edge the tiles are moving towards
+        let coords = |line: i32, pos: i32| match dir {
+            Direction::Up => (line, pos),
+            Direction::Down => (line, height - 1 - pos),
+            Direction::Left => (pos, line),
+            Direction::Right => (width - 1 - pos, line),
+        };
+        for line in 0..line_count {
+            let mut target = 0;
+            for pos in 0..line_length {
+                let (x, y) = coords(line, pos);
+                let idx = self.get_index(x, y);
+                if blocker(&self.tiles[idx]) {
+                    target = pos + 1;
+                } else if movable(&self.tiles[idx]) {
+                    let (tx, ty) = coords(line, target);
+                    let target_idx = self.get_index(tx, ty);
+                    self.tiles.swap(idx, target_idx);
+                    target += 1;
+                }
+            }
+        }
+    }
+
+    /// Get the in-bounds points on the line through `a` and `b` that are spaced by the distance between `a` and `b`.
+    ///
+    /// # Arguments
+    ///
+    /// `a` - first point on the line
+    /// `b` - second point on the line
+    /// `extend` - if false, only `a` and `b` are considered. If true, the line is extended beyond both points until it leaves the map.
+    ///
+    /// # Returns
+    /// The points ordered from the `a` end to the `b` end of the line.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::<char>::new(10, 10, '.');
+    /// assert_eq!(map.points_on_line((4, 3), (5, 5), false), vec![(4, 3), (5, 5)]);
+    /// assert_eq!(
+    ///     map.points_on_line((4, 3), (5, 5), true),
+    ///     vec![(3, 1), (4, 3), (5, 5), (6, 7), (7, 9)]
+    /// );
+    /// assert_eq!(map.points_on_line((0, 0), (0, 0), true), vec![(0, 0)]);
+    /// ```
+    pub fn points_on_line(&self, a: (i32, i32), b: (i32, i32), extend: bool) -> Vec<(i32, i32)> {
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        if (dx, dy) == (0, 0) || !extend {
+            let mut points = vec![a];
+            if b != a {
+                points.push(b);
+            }
+            return points
+                .into_iter()
+                .filter(|(x, y)| self.is_in_bounds(*x, *y))
+                .collect();
+        }
+        let mut start = a;
+        while self.is_in_bounds(start.0 - dx, start.1 - dy) {
+            start = (start.0 - dx, start.1 - dy);
+        }
+        let mut points = vec![];
+        let mut current = start;
+        while self.is_in_bounds(current.0, current.1) {
+            points.push(current);
+            current = (current.0 + dx, current.1 + dy);
+        }
+        points
+    }
+
+    /// Get the in-bounds neighbors of a position whose value satisfies a predicate.
+    ///
+    /// # Arguments
+    ///
+    /// `x` - x coordinate of the position
+    /// `y` - y coordinate of the position
+    /// `diagonal` - if true, diagonal neighbors are included
+    /// `passable` - a closure returning true if the neighbor can be entered
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("#.#\n...\n.##");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.passable_neighbors(0, 1, false, |val| *val == '.'), vec![(1, 1), (0, 2)]);
+    /// assert_eq!(map.passable_neighbors(0, 1, true, |val| *val == '.'), vec![(1, 0), (1, 1), (0, 2)]);
+    /// ```
+    pub fn passable_neighbors<F>(
+        &self,
+        x: i32,
+        y: i32,
+        diagonal: bool,
+        passable: F,
+    ) -> Vec<(i32, i32)>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let offsets = neighbor_offsets(diagonal);
+        offsets
+            .iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|(nx, ny)| self.get(*nx, *ny).is_some_and(&passable))
+            .collect()
+    }
+
+    /// Count the tiles that can be reached from a start position in at most the given number of orthogonal steps,
+    /// where the walker is required to move on every step.
+    /// Since moving back and forth between two tiles takes two steps, a tile counts if its shortest distance
+    /// is at most `steps` and has the same parity as `steps`. For an even budget the start tile itself is included.
+    ///
+    /// # Arguments
+    ///
+    /// `start` - the start position
+    /// `steps` - the number of steps
+    /// `passable` - a closure returning true if a tile can be entered
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from(".....\n.###.\n.....");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.reachable_in((0, 0), 0, |val| *val == '.'), 1);
+    /// assert_eq!(map.reachable_in((0, 0), 1, |val| *val == '.'), 2);
+    /// assert_eq!(map.reachable_in((0, 0), 2, |val| *val == '.'), 3);
+    /// assert_eq!(map.reachable_in((0, 0), 3, |val| *val == '.'), 4);
+    /// ```
+    pub fn reachable_in<F>(&self, start: (i32, i32), steps: usize, passable: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut distances = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some((x, y)) = queue.pop_front() {
+            let distance = distances[&(x, y)];
+            if distance == steps {
+                continue;
+            }
+            for next in self.passable_neighbors(x, y, false, &passable) {
+                if let Entry::Vacant(entry) = distances.entry(next) {
+                    entry.insert(distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+            .values()
+            .filter(|distance| *distance % 2 == steps % 2)
+            .count()
+    }
+
+    /// Get the orthogonal distance of every reachable tile to the nearest of several sources, i.e. for puzzles
+    /// where something spreads from multiple positions at the same time. All sources start at distance 0.
+    /// Sources that are out of bounds are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// `sources` - the start positions
+    /// `passable` - a closure returning true if a tile can be entered
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("O....\n.###.\n....O");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// let distances = map.multi_source_bfs(&[(0, 0), (4, 2)], |val| *val != '#');
+    /// assert_eq!(distances.len(), 12);
+    /// assert_eq!(distances[&(4, 2)], 0);
+    /// assert_eq!(distances[&(2, 0)], 2);
+    /// assert_eq!(distances.values().max(), Some(&3));
+    /// ```
+    pub fn multi_source_bfs<F>(
+        &self,
+        sources: &[(i32, i32)],
+        passable: F,
+    ) -> HashMap<(i32, i32), u64>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        for &source in sources {
+            if self.is_in_bounds(source.0, source.1) && !distances.contains_key(&source) {
+                distances.insert(source, 0);
+                queue.push_back(source);
+            }
+        }
+        while let Some((x, y)) = queue.pop_front() {
+            let distance = distances[&(x, y)];
+            for next in self.passable_neighbors(x, y, false, &passable) {
+                if let Entry::Vacant(entry) = distances.entry(next) {
+                    entry.insert(distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Get the neighbors of a position on a map whose edges wrap around, so every position has the same number of neighbors.
+    /// Coordinates leaving the map on one side enter it on the opposite side. A map without tiles has no neighbors.
+    ///
+    /// # Arguments
+    ///
+    /// `x` - x coordinate of the position
+    /// `y` - y coordinate of the position
+    /// `diagonal` - if true, diagonal neighbors are included
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::<char>::new(4, 3, '.');
+    /// assert_eq!(map.neighbors_wrapping(0, 0, false), vec![(0, 2), (1, 0), (0, 1), (3, 0)]);
+    /// assert_eq!(map.neighbors_wrapping(3, 2, true).len(), 8);
+    ///
+    /// let empty = aoc_lib::map2d::Map2D::<char>::new(0, 0, '.');
+    /// assert!(empty.neighbors_wrapping(0, 0, true).is_empty());
+    /// ```
+    pub fn neighbors_wrapping(&self, x: i32, y: i32, diagonal: bool) -> Vec<(i32, i32)> {
+        if self.width == 0 || self.height == 0 {
+            return vec![];
+        }
+        let offsets = neighbor_offsets(diagonal);
+        offsets
+            .iter()
+            .map(|(dx, dy)| {
+                (
+                    (x + dx).rem_euclid(self.width),
+                    (y + dy).rem_euclid(self.height),
+                )
+            })
+            .collect()
+    }
+
+    /// Count the in-bounds neighbors of a position, for example to tell corners and edges apart from the interior.
+    ///
+    /// # Arguments
+    ///
+    /// `x` - x coordinate of the position
+    /// `y` - y coordinate of the position
+    /// `diagonal` - if true, diagonal neighbors are included
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::<char>::new(3, 3, '.');
+    /// assert_eq!(map.degree(0, 0, false), 2);
+    /// assert_eq!(map.degree(1, 0, false), 3);
+    /// assert_eq!(map.degree(1, 1, false), 4);
+    /// assert_eq!(map.degree(0, 0, true), 3);
+    /// assert_eq!(map.degree(1, 1, true), 8);
+    /// ```
+    pub fn degree(&self, x: i32, y: i32, diagonal: bool) -> usize {
+        let offsets = neighbor_offsets(diagonal);
+        offsets
+            .iter()
+            .filter(|(dx, dy)| self.is_in_bounds(x + dx, y + dy))
+            .count()
+    }
+
+    /// Convert the map into a graph with a node for every tile. Orthogonal neighbors are connected by a directed edge
+    /// if `edge` returns a weight for it.
+    ///
+    /// # Arguments
+    ///
+    /// `edge` - a closure taking the values of the tile an edge starts from and the tile it leads to,
+    /// returning the weight of the edge or `None` if there is no edge
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("12\n#3");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// let graph = map.to_graph(|_, to| to.to_digit(10).map(|d| d as u64));
+    /// assert_eq!(graph.node_count(), 4);
+    /// assert_eq!(graph.get_edges(&(0, 0)), &[((1, 0), 2)]);
+    /// assert_eq!(graph.get_edges(&(1, 1)), &[((1, 0), 2)]);
+    /// assert_eq!(graph.get_edges(&(0, 1)), &[((0, 0), 1), ((1, 1), 3)]);
+    /// ```
+    pub fn to_graph<F>(&self, edge: F) -> Graph<(i32, i32)>
+    where
+        F: Fn(&T, &T) -> Option<u64>,
+    {
+        let mut graph = Graph::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                graph.add_node((x, y));
+                let from = &self.tiles[self.get_index(x, y)];
+                for (dx, dy) in NEIGHBORS {
+                    let weight = self.get(x + dx, y + dy).and_then(|to| edge(from, to));
+                    if let Some(weight) = weight {
+                        graph.add_edge((x, y), (x + dx, y + dy), weight);
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    /// Count the tiles for which a predicate over the tile and the values of its in-bounds neighbors holds.
+    ///
+    /// # Arguments
+    ///
+    /// `diagonal` - if true, diagonal neighbors are included
+    /// `f` - a closure taking the value of a tile and the values of its neighbors, returning true if the tile should be counted
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("@@.\n@@@\n.@.");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// let accessible = map.count_cells_where(true, |val, neighbors| {
+    ///     *val == '@' && neighbors.iter().filter(|n| ***n == '@').count() < 4
+    /// });
+    /// assert_eq!(accessible, 3);
+    /// assert_eq!(map.count_cells_where(false, |_, neighbors| neighbors.len() == 4), 1);
+    /// ```
+    pub fn count_cells_where<F>(&self, diagonal: bool, f: F) -> usize
+    where
+        F: Fn(&T, &[&T]) -> bool,
+    {
+        let offsets = neighbor_offsets(diagonal);
+        let mut count = 0;
+        let mut neighbors = Vec::with_capacity(offsets.len());
+        for y in 0..self.height {
+            for x in 0..self.width {
+                neighbors.clear();
+                neighbors.extend(
+                    offsets
+                        .iter()
+                        .filter_map(|(dx, dy)| self.get(x + dx, y + dy)),
+                );
+                if f(&self.tiles[self.get_index(x, y)], &neighbors) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Run a cellular automaton until the map stops changing. In each step, every tile is replaced at the same time
+    /// by the result of the rule applied to its current value and the values of its in-bounds neighbors, including diagonal ones.
+    ///
+    /// The rule must converge to a fixed point. For a rule that makes the map oscillate or cycle, this never returns.
+    ///
+    /// # Arguments
+    ///
+    /// `rule` - a closure taking the value of a tile and the values of its neighbors, returning the next value of the tile
+    ///
+    /// # Returns
+    /// The number of steps that changed the map. The final step, which leaves the map unchanged, is not counted.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("#...\n....\n....");
+    /// let mut map = aoc_lib::map2d::Map2D::from_string(input);
+    /// let spread = |val: &char, neighbors: &[&char]| match neighbors.contains(&&'#') {
+    ///     true => '#',
+    ///     false => *val,
+    /// };
+    /// assert_eq!(map.step_until_stable(spread), 3);
+    /// assert_eq!(map.get(3, 2), Some(&'#'));
+    /// assert_eq!(map.step_until_stable(spread), 0);
+    /// ```
+    pub fn step_until_stable<F>(&mut self, rule: F) -> usize
+    where
+        T: Clone + PartialEq,
+        F: Fn(&T, &[&T]) -> T,
+    {
+        let mut steps = 0;
+        loop {
+            let mut next_tiles = Vec::with_capacity(self.tiles.len());
+            let mut neighbors = Vec::with_capacity(NEIGHBORS_DIAGONAL.len());
+            for y in 0..self.height {
+                for x in 0..self.width {
+                    neighbors.clear();
+                    neighbors.extend(
+                        NEIGHBORS_DIAGONAL
+                            .iter()
+                            .filter_map(|(dx, dy)| self.get(x + dx, y + dy)),
+                    );
+                    next_tiles.push(rule(&self.tiles[self.get_index(x, y)], &neighbors));
+                }
+            }
+            if next_tiles == self.tiles {
+                return steps;
+            }
+            self.tiles = next_tiles;
+            steps += 1;
+        }
+    }
+
+    /// Sum the cost of entering every tile of a path. The start of the path is not entered, so its cost is skipped.
+    /// Positions out of bounds will be skipped, like in `aggregate_range`.
+    ///
+    /// # Arguments
+    ///
+    /// `path` - the positions of the path, starting with the start position
+    /// `cost` - a closure returning the cost of entering a tile
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("19\n23");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// let cost = |val: &char| val.to_digit(10).unwrap() as u64;
+    /// assert_eq!(map.path_cost(&[(0, 0), (0, 1), (1, 1)], cost), 5);
+    /// assert_eq!(map.path_cost(&[(0, 0), (1, 0), (1, 1)], cost), 12);
+    /// assert_eq!(map.path_cost(&[(0, 0)], cost), 0);
+    /// ```
+    pub fn path_cost<F>(&self, path: &[(i32, i32)], cost: F) -> u64
+    where
+        F: Fn(&T) -> u64,
+    {
+        path.iter()
+            .skip(1)
+            .filter_map(|(x, y)| self.get(*x, *y))
+            .map(cost)
+            .sum()
+    }
+
+    /// Count the tiles strictly inside a closed loop, using a parity scan over every row.
+    /// A tile is inside if the loop crosses the row an odd number of times to its left.
+    ///
+    /// # Arguments
+    ///
+    /// `loop_tiles` - the positions of the loop in walking order. Consecutive positions, including the last and
+    /// the first one, have to be orthogonal neighbors.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::<char>::new(5, 5, '.');
+    /// let ring = [(1, 1), (2, 1), (3, 1), (3, 2), (3, 3), (2, 3), (1, 3), (1, 2)];
+    /// assert_eq!(map.enclosed_area(&ring), 1);
+    ///
+    /// let notched = [
+    ///     (0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (4, 1), (4, 2), (4, 3), (4, 4), (3, 4), (3, 3),
+    ///     (3, 2), (2, 2), (1, 2), (1, 3), (1, 4), (0, 4), (0, 3), (0, 2), (0, 1),
+    /// ];
+    /// assert_eq!(map.enclosed_area(&notched), 3);
+    /// ```
+    pub fn enclosed_area(&self, loop_tiles: &[(i32, i32)]) -> usize {
+        let on_loop: HashSet<(i32, i32)> = loop_tiles.iter().copied().collect();
+        let len = loop_tiles.len();
+        let connects_up: HashSet<(i32, i32)> = (0..len)
+            .filter(|idx| {
+                let (x, y) = loop_tiles[*idx];
+                let prev = loop_tiles[(idx + len - 1) % len];
+                let next = loop_tiles[(idx + 1) % len];
+                prev == (x, y - 1) || next == (x, y - 1)
+            })
+            .map(|idx| loop_tiles[idx])
+            .collect();
+        let mut area = 0;
+        for y in 0..self.height {
+            let mut inside = false;
+            for x in 0..self.width {
+                if connects_up.contains(&(x, y)) {
+                    inside = !inside;
+                } else if inside && !on_loop.contains(&(x, y)) {
+                    area += 1;
+                }
+            }
+        }
+        area
+    }
+
+    /// Find every position where a pattern matches the map. The pattern has to fit into the map entirely.
+    /// A pattern without tiles never matches.
+    ///
+    /// # Arguments
+    ///
+    /// `pattern` - the map to search for
+    /// `wildcard` - tiles of the pattern with this value match any tile
+    ///
+    /// # Returns
+    /// The upper left coordinates of every match, ordered row by row.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::Map2D;
+    /// let map = Map2D::from_string(String::from("#.#.\n.#.#\n#.##"));
+    /// let pattern = Map2D::from_string(String::from("#?\n?#"));
+    /// assert_eq!(map.find_pattern(&pattern, Some(&'?')), vec![(0, 0), (2, 0), (1, 1)]);
+    /// assert_eq!(map.find_pattern(&pattern, None), vec![]);
+    /// assert_eq!(map.find_pattern(&Map2D::new(0, 0, '#'), None), vec![]);
+    /// ```
+    pub fn find_pattern(&self, pattern: &Map2D<T>, wildcard: Option<&T>) -> Vec<(i32, i32)>
+    where
+        T: PartialEq,
+    {
+        if pattern.width == 0 || pattern.height == 0 {
+            return vec![];
+        }
+        let mut matches = vec![];
+        for y in 0..=self.height - pattern.height {
+            for x in 0..=self.width - pattern.width {
+                let found = pattern
+                    .coords_in(0, pattern.width - 1, 0, pattern.height - 1)
+                    .all(|(px, py)| {
+                        let expected = &pattern.tiles[pattern.get_index(px, py)];
+                        Some(expected) == wildcard || self.get(x + px, y + py) == Some(expected)
+                    });
+                if found {
+                    matches.push((x, y));
+                }
+            }
+        }
+        matches
+    }
+
+    /// Compare this map with another map.
+    /// If the maps have different dimensions, coordinates that only exist in one of the maps are treated as differing.
+    ///
+    /// # Returns
+    /// The coordinates where the two maps differ, ordered row by row.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::from_string(String::from("#.\n.."));
+    /// let other = aoc_lib::map2d::Map2D::from_string(String::from("#.\n.#"));
+    /// assert_eq!(map.diff(&other), vec![(1, 1)]);
+    /// assert_eq!(map.diff(&map), vec![]);
+    ///
+    /// let other = aoc_lib::map2d::Map2D::from_string(String::from("#.\n..\n.."));
+    /// assert_eq!(map.diff(&other), vec![(0, 2), (1, 2)]);
+    /// ```
+    pub fn diff(&self, other: &Map2D<T>) -> Vec<(i32, i32)>
+    where
+        T: PartialEq,
+    {
+        let mut coords = vec![];
+        for y in 0..self.height.max(other.height) {
+            for x in 0..self.width.max(other.width) {
+                if self.get(x, y) != other.get(x, y) {
+                    coords.push((x, y));
+                }
+            }
+        }
+        coords
+    }
+
+    /// Get the tiles of every diagonal of the map. Tiles within a diagonal are ordered from top to bottom.
+    ///
+    /// If `anti` is false, the diagonals run from the upper left to the lower right. They are ordered by `x - y`,
+    /// starting with the diagonal containing only the lower left tile.
+    /// If `anti` is true, the diagonals run from the upper right to the lower left. They are ordered by `x + y`,
+    /// starting with the diagonal containing only the upper left tile.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("123\n456");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(
+    ///     map.diagonal(false),
+    ///     vec![vec![&'4'], vec![&'1', &'5'], vec![&'2', &'6'], vec![&'3']]
+    /// );
+    /// assert_eq!(
+    ///     map.diagonal(true),
+    ///     vec![vec![&'1'], vec![&'2', &'4'], vec![&'3', &'5'], vec![&'6']]
+    /// );
+    /// ```
+    pub fn diagonal(&self, anti: bool) -> Vec<Vec<&T>> {
+        if self.width == 0 || self.height == 0 {
+            return vec![];
+        }
+        let mut diagonals = vec![];
+        for start in 0..self.width + self.height - 1 {
+            let diagonal = (0..self.height)
+                .filter_map(|y| {
+                    let x = match anti {
+                        true => start - y,
+                        false => start - (self.height - 1) + y,
+                    };
+                    self.get(x, y)
+                })
+                .collect();
+            diagonals.push(diagonal);
+        }
+        diagonals
+    }
+
+    /// Find a mirror line across which the map reflects onto itself. Rows or columns that have no counterpart
+    /// on the other side of the line are ignored. Vertical lines are checked before horizontal ones.
+    ///
+    /// # Arguments
+    ///
+    /// `smudges` - the exact number of tiles that must differ from their mirrored counterpart
+    ///
+    /// # Returns
+    /// The first mirror line found, or `None` if there is no such line.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::{Map2D, Reflection};
+    /// let input = String::from("#.##..##.\n..#.##.#.\n##......#\n##......#\n..#.##.#.\n..##..##.\n#.#.##.#.");
+    /// let map = Map2D::from_string(input);
+    /// assert_eq!(map.find_reflection(0), Some(Reflection::Vertical(5)));
+    /// assert_eq!(map.find_reflection(1), Some(Reflection::Horizontal(3)));
+    /// assert_eq!(map.find_reflection(100), None);
+    /// ```
+    pub fn find_reflection(&self, smudges: usize) -> Option<Reflection>
+    where
+        T: PartialEq,
+    {
+        let vertical = (1..self.width).find(|col| {
+            let mut mismatches = 0;
+            for y in 0..self.height {
+                for i in 0..(*col).min(self.width - col) {
+                    if self.get(col - 1 - i, y) != self.get(col + i, y) {
+                        mismatches += 1;
+                    }
+                }
+            }
+            mismatches == smudges
+        });
+        if let Some(col) = vertical {
+            return Some(Reflection::Vertical(col));
+        }
+        (1..self.height)
+            .find(|row| {
+                let mut mismatches = 0;
+                for x in 0..self.width {
+                    for i in 0..(*row).min(self.height - row) {
+                        if self.get(x, row - 1 - i) != self.get(x, row + i) {
+                            mismatches += 1;
+                        }
+                    }
+                }
+                mismatches == smudges
+            })
+            .map(Reflection::Horizontal)
+    }
+
+    /// Get all in-bounds coordinates with exactly the given manhattan distance to a center position.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::<char>::new(5, 5, '.');
+    /// assert_eq!(map.ring((2, 2), 0), vec![(2, 2)]);
+    /// assert_eq!(map.ring((2, 2), 1), vec![(2, 1), (3, 2), (2, 3), (1, 2)]);
+    /// assert_eq!(map.ring((2, 2), 2).len(), 8);
+    /// assert_eq!(map.ring((0, 0), 2), vec![(2, 0), (0, 2), (1, 1)]);
+    /// ```
+    pub fn ring(&self, center: (i32, i32), radius: i32) -> Vec<(i32, i32)> {
+        let (cx, cy) = center;
+        if radius == 0 {
+            return match self.is_in_bounds(cx, cy) {
+                true => vec![center],
+                false => vec![],
+            };
+        }
+        let mut coords = vec![];
+        for i in 0..radius {
+            coords.push((cx + i, cy - radius + i));
+            coords.push((cx + radius - i, cy + i));
+            coords.push((cx - i, cy + radius - i));
+            coords.push((cx - radius + i, cy - i));
+        }
+        coords.retain(|(x, y)| self.is_in_bounds(*x, *y));
+        coords
+    }
+
+    /// Replace the region of tiles connected to the start position that have the same value as the start tile.
+    /// Only orthogonal neighbors are considered connected.
+    ///
+    /// # Returns
+    /// The number of tiles changed. Nothing is changed if the start is out of bounds or already has the new value.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("..#\n.##\n#..");
+    /// let mut map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.flood_fill_mut((0, 0), 'O'), 3);
+    /// assert_eq!(map.get(1, 0), Some(&'O'));
+    /// assert_eq!(map.get(1, 2), Some(&'.'));
+    /// assert_eq!(map.flood_fill_mut((0, 0), 'O'), 0);
+    /// ```
+    pub fn flood_fill_mut(&mut self, start: (i32, i32), new_val: T) -> usize
+    where
+        T: Clone + PartialEq,
+    {
+        let old_val = match self.get(start.0, start.1) {
+            Some(val) if *val != new_val => val.clone(),
+            _ => return 0,
+        };
+        let mut changed = 0;
+        let mut stack = vec![start];
+        while let Some((x, y)) = stack.pop() {
+            if self.get(x, y) != Some(&old_val) {
+                continue;
+            }
+            self.set(x, y, new_val.clone());
+            changed += 1;
+            for (dx, dy) in NEIGHBORS {
+                stack.push((x + dx, y + dy));
+            }
+        }
+        changed
+    }
+
+    /// Walk the map from a starting state and check whether the walker ends up in a loop.
+    /// A loop is detected when the same combination of position and direction is visited twice.
+    ///
+    /// # Arguments
+    ///
+    /// `start` - the starting position
+    /// `start_dir` - the starting direction
+    /// `step` - a closure returning the next position and direction, or `None` if the walker leaves the map.
+    /// Returned positions that are out of bounds also end the walk.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::{Direction, Map2D};
+    /// let input = String::from(".#..\n...#\n#...\n..#.");
+    /// let map = Map2D::from_string(input);
+    /// let turn_right = |dir: Direction| match dir {
+    ///     Direction::Up => Direction::Right,
+    ///     Direction::Right => Direction::Down,
+    ///     Direction::Down => Direction::Left,
+    ///     Direction::Left => Direction::Up,
+    /// };
+    /// let step = |(x, y): (i32, i32), dir: Direction| {
+    ///     let (dx, dy) = dir.offset();
+    ///     match map.get(x + dx, y + dy) {
+    ///         None => None,
+    ///         Some('#') => Some(((x, y), turn_right(dir))),
+    ///         Some(_) => Some(((x + dx, y + dy), dir)),
+    ///     }
+    /// };
+    /// assert!(map.detect_loop((1, 2), Direction::Up, step));
+    /// assert!(!map.detect_loop((3, 2), Direction::Up, step));
+    /// ```
+    pub fn detect_loop<F>(&self, start: (i32, i32), start_dir: Direction, step: F) -> bool
+    where
+        F: FnMut((i32, i32), Direction) -> Option<((i32, i32), Direction)>,
+    {
+        let (_, exited) = self.walk(start, start_dir, step);
+        !exited
+    }
+
+    /// Walk the map from a starting state like `detect_loop`, collecting the visited positions.
+    ///
+    /// # Arguments
+    ///
+    /// `start` - the starting position
+    /// `start_dir` - the starting direction
+    /// `step` - a closure returning the next position and direction, or `None` if the walker leaves the map.
+    /// Returned positions that are out of bounds also end the walk.
+    ///
+    /// # Returns
+    /// The distinct in-bounds positions visited, and true if the walker left the map or false if it ended up in a loop.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::{Direction, Map2D};
+    /// let input = String::from(".#..\n....\n....");
+    /// let map = Map2D::from_string(input);
+    /// let turn_right = |dir: Direction| match dir {
+    ///     Direction::Up => Direction::Right,
+    ///     Direction::Right => Direction::Down,
+    ///     Direction::Down => Direction::Left,
+    ///     Direction::Left => Direction::Up,
+    /// };
+    /// let step = |(x, y): (i32, i32), dir: Direction| {
+    ///     let (dx, dy) = dir.offset();
+    ///     match map.get(x + dx, y + dy) {
+    ///         Some('#') => Some(((x, y), turn_right(dir))),
+    ///         _ => Some(((x + dx, y + dy), dir)),
+    ///     }
+    /// };
+    /// let (visited, exited) = map.walk((1, 2), Direction::Up, step);
+    /// assert!(exited);
+    /// assert_eq!(visited.len(), 4);
+    /// assert!(visited.contains(&(1, 1)) && visited.contains(&(3, 1)));
+    /// ```
+    pub fn walk<F>(
+        &self,
+        start: (i32, i32),
+        start_dir: Direction,
+        mut step: F,
+    ) -> (HashSet<(i32, i32)>, bool)
+    where
+        F: FnMut((i32, i32), Direction) -> Option<((i32, i32), Direction)>,
+    {
+        let mut states = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut state = (start, start_dir);
+        while self.is_in_bounds(state.0 .0, state.0 .1) {
+            if !states.insert(state) {
+                return (visited, false);
+            }
+            visited.insert(state.0);
+            match step(state.0, state.1) {
+                Some(next) => state = next,
+                None => return (visited, true),
+            }
+        }
+        (visited, true)
+    }
+
+    /// Follow a beam through the map, where tiles may redirect, split or absorb it, i.e. mirrors and splitters.
+    /// Beams leaving the map disappear. The simulation ends once every beam has left the map, was absorbed,
+    /// or reached a combination of position and direction that was already visited.
+    ///
+    /// # Arguments
+    ///
+    /// `start` - the position of the first tile the beam enters
+    /// `dir` - the direction the beam is moving in when it enters the first tile
+    /// `interact` - a closure receiving the value of a tile and the direction of an incoming beam,
+    /// returning the directions of the outgoing beams. An empty result absorbs the beam.
+    ///
+    /// # Returns
+    /// The positions of all tiles a beam has passed through.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::{Direction, Map2D};
+    /// let input = String::from("..|\n...\n...");
+    /// let map = Map2D::from_string(input);
+    /// let interact = |val: &char, dir: Direction| match (val, dir) {
+    ///     ('|', Direction::Left | Direction::Right) => vec![Direction::Up, Direction::Down],
+    ///     _ => vec![dir],
+    /// };
+    /// let energized = map.simulate_beam((0, 0), Direction::Right, interact);
+    /// assert_eq!(energized.len(), 5);
+    /// assert!(energized.contains(&(2, 2)));
+    /// assert!(!energized.contains(&(1, 1)));
+    /// ```
+    pub fn simulate_beam<F>(
+        &self,
+        start: (i32, i32),
+        dir: Direction,
+        interact: F,
+    ) -> HashSet<(i32, i32)>
+    where
+        F: Fn(&T, Direction) -> Vec<Direction>,
+    {
+        let mut states = HashSet::new();
+        let mut stack = vec![(start, dir)];
+        while let Some(((x, y), dir)) = stack.pop() {
+            let val = match self.get(x, y) {
+                Some(val) => val,
+                None => continue,
+            };
+            if !states.insert(((x, y), dir)) {
+                continue;
+            }
+            for next_dir in interact(val, dir) {
+                let (dx, dy) = next_dir.offset();
+                stack.push(((x + dx, y + dy), next_dir));
+            }
+        }
+        states.into_iter().map(|(pos, _)| pos).collect()
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+}
+
+impl Map2D<char> {
+    /// Create a new map out of an input string. If the input string contains any empty lines, the remainder of the input will be ignored.
+    /// Lines may end with `\n` or `\r\n`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the input is empty or any line in the string has a different length than the first one.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("12345\n12345");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// ```
+    pub fn from_string(input: String) -> Map2D<char> {
+        Map2D::try_from_string(input).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Create a new map out of an input string like `from_string`, returning an error instead of panicking on invalid input.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Map2DError::Empty` if the input contains no lines
+    /// and `Map2DError::RaggedRows` if any line has a different length than the first one.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::{Map2D, Map2DError};
+    /// let map = Map2D::try_from_string(String::from("123\n456")).unwrap();
+    /// assert_eq!(map.get(2, 1), Some(&'6'));
+    ///
+    /// assert_eq!(Map2D::try_from_string(String::new()).err(), Some(Map2DError::Empty));
+    /// assert_eq!(
+    ///     Map2D::try_from_string(String::from("123\n45")).err(),
+    ///     Some(Map2DError::RaggedRows { row: 1, expected: 3, found: 2, line: String::from("45") })
+    /// );
+    /// ```
+    pub fn try_from_string(input: String) -> Result<Map2D<char>, Map2DError> {
+        let split: Vec<&str> = input_lines(&input).collect();
+        let width = match split.first() {
+            Some(line) => line.len(),
+            None => return Err(Map2DError::Empty),
+        };
+        for (idx, line) in split.iter().enumerate() {
+            if line.len() != width {
+                return Err(Map2DError::RaggedRows {
+                    row: idx,
+                    expected: width,
+                    found: line.len(),
+                    line: String::from(*line),
+                });
+            }
+        }
+        let height = split.len();
+        Ok(Map2D {
+            tiles: split.join("").chars().collect(),
+            width: width as i32,
+            height: height as i32,
+        })
+    }
+
+    /// Create a new map out of an input string like `try_from_string`, checking that every tile is one of the allowed characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `try_from_string`
+    /// and `Map2DError::InvalidTile` with the position of the first tile that is not allowed, searching row by row.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::{Map2D, Map2DError};
+    /// let map = Map2D::from_string_checked(String::from("#.\n.#"), &['#', '.']).unwrap();
+    /// assert_eq!(map.get(1, 1), Some(&'#'));
+    ///
+    /// assert_eq!(
+    ///     Map2D::from_string_checked(String::from("#.\n.x"), &['#', '.']).err(),
+    ///     Some(Map2DError::InvalidTile { x: 1, y: 1, tile: 'x' })
+    /// );
+    /// ```
+    pub fn from_string_checked(input: String, allowed: &[char]) -> Result<Map2D<char>, Map2DError> {
+        let map = Map2D::try_from_string(input)?;
+        match map.tiles.iter().position(|tile| !allowed.contains(tile)) {
+            Some(idx) => Err(Map2DError::InvalidTile {
+                x: idx as i32 % map.width,
+                y: idx as i32 / map.width,
+                tile: map.tiles[idx],
+            }),
+            None => Ok(map),
+        }
+    }
+
+    /// Create a new map out of an input string, padding lines shorter than the longest line with the given fill character.
+    /// If the input string contains any empty lines, the remainder of the input will be ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("1\n123\n12");
+    /// let map = aoc_lib::map2d::Map2D::from_string_padded(input, ' ');
+    /// assert_eq!(map.width(), 3);
+    /// assert_eq!(map.height(), 3);
+    /// assert_eq!(map.get(1, 0), Some(&' '));
+    /// assert_eq!(map.get(2, 1), Some(&'3'));
+    /// assert_eq!(map.get(2, 2), Some(&' '));
+    /// ```
+    pub fn from_string_padded(input: String, fill: char) -> Map2D<char> {
+        let split: Vec<&str> = input_lines(&input).collect();
+        let width = split
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let height = split.len();
+        let mut tiles = Vec::with_capacity(width * height);
+        for line in split {
+            let len_before = tiles.len();
+            tiles.extend(line.chars());
+            tiles.resize(len_before + width, fill);
+        }
+        Map2D {
+            tiles,
+            width: width as i32,
+            height: height as i32,
+        }
+    }
+
+    /// Create a new map by reading lines from a reader. If the input contains any empty lines, the remainder of the input will be ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails or if any line has a different length than the first one.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = "123\n456\n";
+    /// let map = aoc_lib::map2d::Map2D::from_reader(input.as_bytes()).unwrap();
+    /// assert_eq!(map.width(), 3);
+    /// assert_eq!(map.get(2, 1), Some(&'6'));
+    ///
+    /// let input = "123\n45\n";
+    /// assert!(aoc_lib::map2d::Map2D::from_reader(input.as_bytes()).is_err());
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Map2D<char>> {
+        let mut tiles = vec![];
+        let mut width = 0;
+        let mut height = 0;
+        for line in reader.lines() {
+            let line = line?;
+            if line.is_empty() {
+                break;
+            }
+            let len_before = tiles.len();
+            tiles.extend(line.chars());
+            let line_width = tiles.len() - len_before;
+            if height == 0 {
+                width = line_width;
+            } else if line_width != width {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Invalid length on line {height}. Expected {width}. Found {line_width}. Full line: {line}"
+                    ),
+                ));
+            }
+            height += 1;
+        }
+        Ok(Map2D {
+            tiles,
+            width: width as i32,
+            height,
+        })
+    }
+
+    /// Render the tiles marked with `#` as ASCII art, trimmed to the bounding box of the marked tiles.
+    /// Marked tiles are rendered as `#`, all others as `.`.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("......\n.#..#.\n.####.\n......");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.render_letters(), "#..#\n####");
+    /// ```
+    pub fn render_letters(&self) -> String {
+        self.letter_rows().join("\n")
+    }
+
+    /// Decode letters written in the 4x6 font used by the puzzles. The letters are read from the tiles marked with `#`.
+    ///
+    /// # Returns
+    /// The decoded letters, or `None` if the marked tiles don't form letters of the known font.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from(
+    ///     "#..#.####\n#..#.#...\n####.###.\n#..#.#...\n#..#.#...\n#..#.####",
+    /// );
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.decode_letters(), Some(String::from("HE")));
+    /// ```
+    pub fn decode_letters(&self) -> Option<String> {
+        let rows = self.letter_rows();
+        if rows.len() != LETTER_HEIGHT {
+            return None;
+        }
+        let width = rows[0].len();
+        let mut letters = String::new();
+        for start in (0..width).step_by(LETTER_WIDTH + 1) {
+            let mut pattern = String::new();
+            for row in &rows {
+                let end = width.min(start + LETTER_WIDTH);
+                pattern.push_str(&format!("{:.<LETTER_WIDTH$}", &row[start..end]));
+            }
+            let (letter, _) = LETTERS.iter().find(|(_, shape)| *shape == pattern)?;
+            letters.push(*letter);
+        }
+        Some(letters)
+    }
+
+    /// Count the occurrences of a word in the map, reading from every tile in all eight directions.
+    /// Reversed occurrences are found through the opposite direction. A single-character word is counted once per matching tile.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = String::from("XMAS\nMM..\nA.A.\nS..S");
+    /// let map = aoc_lib::map2d::Map2D::from_string(input);
+    /// assert_eq!(map.count_word("XMAS"), 3);
+    /// assert_eq!(map.count_word("SAMX"), 3);
+    /// assert_eq!(map.count_word("X"), 1);
+    /// ```
+    pub fn count_word(&self, word: &str) -> usize {
+        let chars: Vec<char> = word.chars().collect();
+        if chars.is_empty() {
+            return 0;
+        }
+        let directions = match chars.len() {
+            1 => vec![Direction8::Up],
+            _ => Direction8::all().to_vec(),
+        };
+        let mut count = 0;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                for dir in &directions {
+                    let (dx, dy) = dir.offset();
+                    let found = chars.iter().enumerate().all(|(i, c)| {
+                        let i = i as i32;
+                        self.get(x + dx * i, y + dy * i) == Some(c)
+                    });
+                    if found {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    fn letter_rows(&self) -> Vec<String> {
+        let marked: Vec<(i32, i32)> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|(x, y)| self.get(*x, *y) == Some(&'#'))
+            .collect();
+        if marked.is_empty() {
+            return vec![];
+        }
+        let min_x = marked.iter().map(|p| p.0).min().unwrap();
+        let max_x = marked.iter().map(|p| p.0).max().unwrap();
+        let min_y = marked.iter().map(|p| p.1).min().unwrap();
+        let max_y = marked.iter().map(|p| p.1).max().unwrap();
+        (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| match self.get(x, y) {
+                        Some('#') => '#',
+                        _ => '.',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Map2D<bool> {
+    /// Create a new map covering the bounding box of the given points. Listed points are set to true, all others to false.
+    /// Coordinates are shifted so the smallest x and y coordinates of the points map to 0.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::from_points(&[(2, 5), (4, 6)]);
+    /// assert_eq!(map.width(), 3);
+    /// assert_eq!(map.height(), 2);
+    /// assert_eq!(map.get(0, 0), Some(&true));
+    /// assert_eq!(map.get(2, 1), Some(&true));
+    /// assert_eq!(map.get(1, 0), Some(&false));
+    /// ```
+    pub fn from_points(points: &[(i32, i32)]) -> Map2D<bool> {
+        if points.is_empty() {
+            return Map2D::new(0, 0, false);
+        }
+        let min_x = points.iter().map(|p| p.0).min().unwrap();
+        let max_x = points.iter().map(|p| p.0).max().unwrap();
+        let min_y = points.iter().map(|p| p.1).min().unwrap();
+        let max_y = points.iter().map(|p| p.1).max().unwrap();
+        let mut map = Map2D::new(max_x - min_x + 1, max_y - min_y + 1, false);
+        for (x, y) in points {
+            map.set(x - min_x, y - min_y, true);
+        }
+        map
+    }
+}
+
+impl Map2D<u32> {
+    /// Create a new map out of an input string where every tile is a single decimal digit, i.e. a height map.
+    /// If the input string contains any empty lines, the remainder of the input will be ignored.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the input is not rectangular or contains a character that is not a digit.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = aoc_lib::map2d::Map2D::digit_grid(String::from("2199\n3987"));
+    /// assert_eq!(map.get(1, 0), Some(&1));
+    /// assert_eq!(map.get(3, 1), Some(&7));
+    /// ```
+    pub fn digit_grid(input: String) -> Map2D<u32> {
+        let chars = Map2D::try_from_string(input).unwrap_or_else(|err| panic!("{err}"));
+        let tiles = chars
+            .tiles
+            .iter()
+            .enumerate()
+            .map(|(idx, c)| {
+                c.to_digit(10).unwrap_or_else(|| {
+                    let (x, y) = chars.get_coords(idx);
+                    panic!("Invalid digit '{c}' at ({x}, {y}).")
+                })
+            })
+            .collect();
+        Map2D {
+            tiles,
+            width: chars.width,
+            height: chars.height,
+        }
+    }
+}
+
+impl Map2D<f64> {
+    /// Check whether two maps are equal within a tolerance, i.e. to detect when an iterative simulation has converged.
+    ///
+    /// # Returns
+    /// `true` if both maps have the same dimensions and every pair of corresponding tiles differs by less than `epsilon`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_lib::map2d::Map2D;
+    /// let mut a = Map2D::new(2, 2, 1.0);
+    /// let b = Map2D::new(2, 2, 1.0);
+    /// a.set(1, 1, 1.0005);
+    /// assert!(a.approx_eq(&b, 0.001));
+    /// assert!(!a.approx_eq(&b, 0.0001));
+    /// assert!(!a.approx_eq(&Map2D::new(2, 1, 1.0), 0.001));
+    /// ```
+    pub fn approx_eq(&self, other: &Map2D<f64>, epsilon: f64) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self
+                .tiles
+                .iter()
+                .zip(&other.tiles)
+                .all(|(a, b)| (a - b).abs() < epsilon)
+    }
+}
+
+/// Splits an input into lines up to the first empty line. A trailing `\r` is removed from every line,
+/// so inputs with Windows line endings are handled like inputs with Unix line endings.
+fn input_lines(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .take_while(|line| !line.is_empty())
+}
+
+/// Returns a uniformly distributed number in `0..bound`. Rolls from the incomplete block at the top of the
+/// range are rejected, since mapping them with `%` would favor small results.
+#[cfg(feature = "rand")]
+fn random_below(state: &mut u64, bound: u64) -> u64 {
+    let limit = u64::MAX - u64::MAX % bound;
+    loop {
+        let roll = splitmix64(state);
+        if roll < limit {
+            return roll % bound;
+        }
+    }
+}
+
+/// Advances the state of a SplitMix64 generator and returns the next pseudo-random number.
+#[cfg(feature = "rand")]
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "Found 5. Full line: 12345")]
+    fn test_from_string_panics() {
+        let input = String::from("123456\n12345");
+        Map2D::from_string(input);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_random() {
+        let choices = [('#', 1), ('.', 0), ('O', 2)];
+        let map = Map2D::random(30, 30, &choices, 7);
+        assert_eq!(map.tiles.len(), 900);
+        assert!(!map.tiles.contains(&'.'));
+        assert!(map.tiles.contains(&'#') && map.tiles.contains(&'O'));
+        assert_eq!(map.tiles, Map2D::random(30, 30, &choices, 7).tiles);
+        assert_ne!(map.tiles, Map2D::random(30, 30, &choices, 8).tiles);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid digit 'x' at (1, 1).")]
+    fn test_digit_grid_panics() {
+        let input = String::from("12\n3x");
+        Map2D::digit_grid(input);
+    }
+
+    #[test]
+    fn test_from_string() {
+        let input = String::from("12345\n12345\n12345");
+        let map = Map2D::from_string(input);
+        assert_eq!(map.width, 5);
+        assert_eq!(map.height, 3);
+
+        let input = String::from("12345\n12345\n12345\n");
+        let map = Map2D::from_string(input);
+        assert_eq!(map.width, 5);
+        assert_eq!(map.height, 3);
+
+        let input = String::from("12345\n12345\n12345\n\n12345");
+        let map = Map2D::from_string(input);
+        assert_eq!(map.width, 5);
+        assert_eq!(map.height, 3);
+    }
+
+    #[test]
+    fn test_from_string_crlf() {
+        let input = String::from("123\r\n456\r\n\r\n789");
+        let map = Map2D::from_string(input);
+        assert_eq!(map.width, 3);
+        assert_eq!(map.height, 2);
+        assert_eq!(map.get(2, 1), Some(&'6'));
+
+        let map = Map2D::from_string_padded(String::from("1\r\n123\r\n"), ' ');
+        assert_eq!(map.width, 3);
+        assert_eq!(map.get(1, 0), Some(&' '));
+    }
+
+    #[test]
+    fn test_count_corners() {
+        let input = String::from("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        let map = Map2D::from_string(input);
+        let mut region = vec![];
+        for x in 0..map.width {
+            for y in 0..map.height {
+                if map.get(x, y) == Some(&'A') {
+                    region.push((x, y));
+                }
+            }
+        }
+        assert_eq!(map.count_corners(&region), 12);
+        assert_eq!(map.count_corners(&[(3, 1), (4, 1), (3, 2), (4, 2)]), 4);
+
+        let ring = [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (0, 1),
+            (2, 1),
+            (0, 2),
+            (1, 2),
+            (2, 2),
+        ];
+        assert_eq!(map.count_corners(&ring), 8);
+    }
+
+    #[test]
+    fn test_print() {
         let input = String::from("12345\n12345\n12345");
         let map = Map2D::from_string(input);
         map.print();
     }
+
+    #[test]
+    fn test_decode_letters() {
+        let mut rows = vec![String::new(); LETTER_HEIGHT];
+        for (_, shape) in LETTERS {
+            for (y, row) in rows.iter_mut().enumerate() {
+                row.push_str(&shape[y * LETTER_WIDTH..(y + 1) * LETTER_WIDTH]);
+                row.push('.');
+            }
+        }
+        let map = Map2D::from_string(rows.join("\n"));
+        let expected: String = LETTERS.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(map.decode_letters(), Some(expected));
+
+        let map = Map2D::from_string(String::from("#.\n.#"));
+        assert_eq!(map.decode_letters(), None);
+    }
+
+    #[test]
+    fn test_count_word() {
+        let input = String::from(
+            "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX",
+        );
+        let map = Map2D::from_string(input);
+        assert_eq!(map.count_word("XMAS"), 18);
+        assert_eq!(map.count_word("SAMX"), 18);
+        assert_eq!(map.count_word(""), 0);
+
+        let map = Map2D::from_string(String::from("ABA"));
+        assert_eq!(map.count_word("ABA"), 2);
+    }
 }
@@ -1,11 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
 use std::ops::Add;
+
+use crate::util::Monoid;
 use tree_node::TreeNode;
 
 /// Represents a tree data structure.
+/// Clones keep the ids of all nodes. Two trees are equal if their nodes have the same ids, values and links.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Tree<T> {
     nodes: HashMap<usize, TreeNode<T>>,
     node_count: usize,
+    root_id: usize,
 }
 
 impl<T> Tree<T> {
@@ -17,6 +23,7 @@ impl<T> Tree<T> {
         let mut tree = Tree {
             nodes: HashMap::new(),
             node_count: 0,
+            root_id: 0,
         };
 
         tree.create_node(val);
@@ -39,21 +46,117 @@ impl<T> Tree<T> {
     }
 
     pub fn get_parent_id(&self, id: usize) -> &Option<usize> {
-        &self.nodes.get(&id).unwrap().get_parent_id()
+        self.nodes.get(&id).unwrap().get_parent_id()
     }
 
     pub fn get_child_ids(&self, id: usize) -> &Vec<usize> {
-        &self.nodes.get(&id).unwrap().get_child_ids()
+        self.nodes.get(&id).unwrap().get_child_ids()
+    }
+
+    /// Get an iterator over the ancestors of a node, starting with its parent and ending with the root node.
+    /// The node itself is not included.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new("/");
+    /// let a = tree.add_child(0, "a");
+    /// let e = tree.add_child(a, "e");
+    /// assert_eq!(tree.ancestors(e).collect::<Vec<_>>(), vec![a, 0]);
+    /// assert_eq!(tree.ancestors(0).count(), 0);
+    /// assert_eq!(tree.ancestors(e).find(|id| *tree.get_val(*id) == "/"), Some(0));
+    /// ```
+    pub fn ancestors(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(*self.get_parent_id(id), |current_id| {
+            *self.get_parent_id(*current_id)
+        })
+    }
+
+    /// Get the ids of the nodes on the path between two nodes, leading up to their lowest common ancestor and back down.
+    /// The number of edges on the path is one less than the number of returned ids.
+    ///
+    /// # Returns
+    /// The node ids from `a` to `b`, both included, or `None` if either id is not part of the tree.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new("COM");
+    /// let b = tree.add_child(0, "B");
+    /// let you = tree.add_child(b, "YOU");
+    /// let c = tree.add_child(0, "C");
+    /// let san = tree.add_child(c, "SAN");
+    /// assert_eq!(tree.path_between(you, san), Some(vec![you, b, 0, c, san]));
+    /// assert_eq!(tree.path_between(b, you), Some(vec![b, you]));
+    /// assert_eq!(tree.path_between(b, b), Some(vec![b]));
+    /// assert_eq!(tree.path_between(b, 100), None);
+    /// ```
+    pub fn path_between(&self, a: usize, b: usize) -> Option<Vec<usize>> {
+        if !self.nodes.contains_key(&a) || !self.nodes.contains_key(&b) {
+            return None;
+        }
+        let mut up: Vec<usize> = std::iter::once(a).chain(self.ancestors(a)).collect();
+        let mut down: Vec<usize> = std::iter::once(b).chain(self.ancestors(b)).collect();
+        let mut common_ancestor = None;
+        while !up.is_empty() && up.last() == down.last() {
+            common_ancestor = up.pop();
+            down.pop();
+        }
+        up.push(common_ancestor?);
+        up.extend(down.into_iter().rev());
+        Some(up)
+    }
+
+    /// Check whether a node lies on the path from another node to the root node. A node is not its own ancestor.
+    ///
+    /// # Arguments
+    ///
+    /// `ancestor` - id of the potential ancestor
+    /// `descendant` - id of the node whose ancestors are checked
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new("/");
+    /// let a = tree.add_child(0, "a");
+    /// let e = tree.add_child(a, "e");
+    /// let b = tree.add_child(0, "b");
+    /// assert!(tree.is_ancestor(0, e));
+    /// assert!(tree.is_ancestor(a, e));
+    /// assert!(!tree.is_ancestor(b, e));
+    /// assert!(!tree.is_ancestor(e, e));
+    /// ```
+    pub fn is_ancestor(&self, ancestor: usize, descendant: usize) -> bool {
+        self.ancestors(descendant).any(|id| id == ancestor)
+    }
+
+    /// Get the values of the children of a node, in the order the children were added.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new('+');
+    /// tree.add_child(0, '1');
+    /// tree.add_child(0, '2');
+    /// assert_eq!(tree.child_vals(0), vec![&'1', &'2']);
+    /// assert!(tree.child_vals(1).is_empty());
+    /// ```
+    pub fn child_vals(&self, id: usize) -> Vec<&T> {
+        self.get_child_ids(id)
+            .iter()
+            .map(|child| self.get_val(*child))
+            .collect()
     }
 
     fn get_node(&self, id: usize) -> &TreeNode<T> {
-        &self.nodes.get(&id).unwrap()
+        self.nodes.get(&id).unwrap()
     }
 
     fn get_mut_node(&mut self, id: usize) -> &mut TreeNode<T> {
         self.nodes.get_mut(&id).unwrap()
     }
 
+    /// Get the id of the root node. This is 0 unless the tree has been rerooted.
+    pub fn get_root_id(&self) -> usize {
+        self.root_id
+    }
+
     /// Get the number of nodes in the tree.
     pub fn get_node_count(&self) -> usize {
         self.node_count
@@ -62,7 +165,7 @@ impl<T> Tree<T> {
     /// Adds a new node to the tree.
     ///
     /// # Arguments
-    /// * `parent_id` - id of the parent node. The id of the root node is always 0.
+    /// * `parent_id` - id of the parent node. The id of the root node is 0 unless the tree has been rerooted.
     /// * `val` - value of the child node
     ///
     /// # Returns
@@ -76,6 +179,38 @@ impl<T> Tree<T> {
         child_id
     }
 
+    /// Adds a new node to the tree and passes its id to a closure, so nested structures can be built inline.
+    ///
+    /// # Arguments
+    /// * `parent_id` - id of the parent node. The id of the root node is 0 unless the tree has been rerooted.
+    /// * `val` - value of the child node
+    /// * `f` - a closure receiving the tree and the id of the created child node
+    ///
+    /// # Returns
+    /// The id of the created child node.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new("/");
+    /// let a = tree.add_child_with(0, "a", |tree, a| {
+    ///     tree.add_child_with(a, "e", |tree, e| {
+    ///         tree.add_child(e, "i");
+    ///     });
+    ///     tree.add_child(a, "f");
+    /// });
+    /// tree.add_child(0, "d");
+    /// assert_eq!(tree.get_node_count(), 6);
+    /// assert_eq!(tree.child_vals(a), vec![&"e", &"f"]);
+    /// ```
+    pub fn add_child_with<F>(&mut self, parent_id: usize, val: T, f: F) -> usize
+    where
+        F: FnOnce(&mut Tree<T>, usize),
+    {
+        let child_id = self.add_child(parent_id, val);
+        f(self, child_id);
+        child_id
+    }
+
     /// Aggregates values in the tree into a single value.
     ///
     /// # Arguments
@@ -93,8 +228,7 @@ impl<T> Tree<T> {
         let mut stack = vec![start_id];
         let mut value: Option<R> = None;
 
-        while stack.len() > 0 {
-            let current_id = stack.pop().unwrap();
+        while let Some(current_id) = stack.pop() {
             let current_node = self.get_node(current_id);
             if current_node.has_children() {
                 for child in current_node.get_child_ids() {
@@ -122,12 +256,476 @@ impl<T> Tree<T> {
         R: Add<Output = R>,
         F: FnOnce(usize, &T) -> R + Copy,
     {
-        self.aggregate(0, f)
+        self.aggregate(self.root_id, f)
+    }
+
+    /// Aggregates values in the tree for every subtree at once, in a single post-order pass starting from the root node.
+    ///
+    /// # Arguments
+    ///
+    /// `f` - a closure returning the value that should be aggregated
+    ///
+    /// # Returns
+    /// The aggregated value of the subtree of every node, mapped by node id.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new(0);
+    /// let a = tree.add_child(0, 0);
+    /// tree.add_child(a, 100);
+    /// tree.add_child(a, 50);
+    /// tree.add_child(0, 200);
+    /// let sizes = tree.aggregate_all(|_, size| *size);
+    /// assert_eq!(sizes[&0], 350);
+    /// assert_eq!(sizes[&a], 150);
+    /// assert_eq!(sizes.values().filter(|size| **size <= 150).sum::<i32>(), 300);
+    /// ```
+    pub fn aggregate_all<F, R>(&self, f: F) -> HashMap<usize, R>
+    where
+        R: Add<Output = R> + Clone,
+        F: FnOnce(usize, &T) -> R + Copy,
+    {
+        let mut values: HashMap<usize, R> = HashMap::new();
+        for id in self.post_order(self.root_id) {
+            let value = self
+                .get_child_ids(id)
+                .iter()
+                .fold(f(id, self.get_val(id)), |acc, child| {
+                    acc + values[child].clone()
+                });
+            values.insert(id, value);
+        }
+        values
+    }
+
+    /// Aggregates values in a subtree into a single value, combining them with the `Monoid` implementation of the result type.
+    /// Values are combined in pre-order, so collecting into a `Vec` or `String` keeps the order of `pre_order`.
+    ///
+    /// # Arguments
+    ///
+    /// `start_id` - id of the node the aggregation should start from
+    /// `f` - a closure returning the value that should be aggregated
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new("a");
+    /// let b = tree.add_child(0, "b");
+    /// tree.add_child(b, "c");
+    /// tree.add_child(0, "d");
+    /// assert_eq!(tree.aggregate_monoid(0, |_, val| val.to_string()), "abcd");
+    /// assert_eq!(tree.aggregate_monoid(b, |id, _| vec![id]), vec![1, 2]);
+    /// ```
+    pub fn aggregate_monoid<F, R>(&self, start_id: usize, f: F) -> R
+    where
+        R: Monoid,
+        F: Fn(usize, &T) -> R,
+    {
+        self.pre_order(start_id)
+            .into_iter()
+            .fold(R::empty(), |acc, id| acc.combine(f(id, self.get_val(id))))
+    }
+
+    /// Folds the values in a subtree into an accumulator of any type. Values are visited in pre-order.
+    ///
+    /// # Arguments
+    ///
+    /// `start` - id of the node the fold should start from
+    /// `init` - the initial value of the accumulator
+    /// `f` - a closure taking the accumulator and the value of a node, returning the next accumulator
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new("/");
+    /// let a = tree.add_child(0, "a");
+    /// tree.add_child(a, "e");
+    /// tree.add_child(0, "d");
+    /// assert_eq!(tree.fold(0, String::new(), |acc, val| acc + val), "/aed");
+    /// assert_eq!(tree.fold(a, None, |acc: Option<&str>, val| acc.max(Some(*val))), Some("e"));
+    /// ```
+    pub fn fold<B, F>(&self, start: usize, init: B, f: F) -> B
+    where
+        F: Fn(B, &T) -> B,
+    {
+        self.pre_order(start)
+            .into_iter()
+            .fold(init, |acc, id| f(acc, self.get_val(id)))
+    }
+
+    /// Count the nodes in the tree whose value satisfies a predicate.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new(0);
+    /// let a = tree.add_child(0, 0);
+    /// tree.add_child(a, 100);
+    /// tree.add_child(0, 200);
+    /// let sizes = tree.aggregate_all(|_, size| *size);
+    /// assert_eq!(tree.count(|val| *val >= 100), 2);
+    /// assert_eq!(sizes.values().filter(|size| **size <= 100).count(), 2);
+    /// ```
+    pub fn count<F>(&self, f: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.nodes.values().filter(|node| f(&node.val)).count()
+    }
+
+    /// Get the ids of a subtree in pre-order. Every node appears before all of its descendants.
+    /// Children are visited in the order they were added.
+    ///
+    /// # Arguments
+    ///
+    /// `start` - id of the node the traversal should start from
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new(1);
+    /// let a = tree.add_child(0, 2);
+    /// let b = tree.add_child(0, 3);
+    /// let c = tree.add_child(a, 4);
+    /// assert_eq!(tree.pre_order(0), vec![0, a, c, b]);
+    /// ```
+    pub fn pre_order(&self, start: usize) -> Vec<usize> {
+        let mut order = vec![];
+        let mut stack = vec![start];
+        while let Some(current_id) = stack.pop() {
+            order.push(current_id);
+            for child in self.get_child_ids(current_id).iter().rev() {
+                stack.push(*child);
+            }
+        }
+        order
+    }
+
+    /// Get the ids of a subtree in post-order. Every node appears after all of its descendants.
+    /// Children are visited in the order they were added.
+    ///
+    /// # Arguments
+    ///
+    /// `start` - id of the node the traversal should start from
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new(1);
+    /// let a = tree.add_child(0, 2);
+    /// let b = tree.add_child(0, 3);
+    /// let c = tree.add_child(a, 4);
+    /// assert_eq!(tree.post_order(0), vec![c, a, b, 0]);
+    /// ```
+    pub fn post_order(&self, start: usize) -> Vec<usize> {
+        let mut order = vec![];
+        let mut stack = vec![start];
+        while let Some(current_id) = stack.pop() {
+            order.push(current_id);
+            for child in self.get_child_ids(current_id) {
+                stack.push(*child);
+            }
+        }
+        order.reverse();
+        order
+    }
+
+    /// Get the depth of every node in the tree, computed in a single pass starting from the root node.
+    /// The root node has a depth of 0.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new(1);
+    /// let a = tree.add_child(0, 2);
+    /// let b = tree.add_child(a, 3);
+    /// let depths = tree.depths();
+    /// assert_eq!(depths[&0], 0);
+    /// assert_eq!(depths[&a], 1);
+    /// assert_eq!(depths[&b], 2);
+    /// ```
+    pub fn depths(&self) -> HashMap<usize, usize> {
+        let mut depths = HashMap::from([(self.root_id, 0)]);
+        let mut queue = VecDeque::from([self.root_id]);
+        while let Some(current_id) = queue.pop_front() {
+            let depth = depths[&current_id];
+            for child in self.get_child_ids(current_id) {
+                depths.insert(*child, depth + 1);
+                queue.push_back(*child);
+            }
+        }
+        depths
+    }
+
+    /// Get the ids of all nodes grouped by their depth. The first level only contains the root node.
+    /// Within a level, nodes are ordered like in a breadth-first traversal with children in the order they were added.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new("/");
+    /// let a = tree.add_child(0, "a");
+    /// let d = tree.add_child(0, "d");
+    /// let e = tree.add_child(a, "e");
+    /// let f = tree.add_child(d, "f");
+    /// assert_eq!(tree.levels(), vec![vec![0], vec![a, d], vec![e, f]]);
+    /// ```
+    pub fn levels(&self) -> Vec<Vec<usize>> {
+        let mut levels = vec![];
+        let mut level = vec![self.root_id];
+        while !level.is_empty() {
+            let next_level = level
+                .iter()
+                .flat_map(|id| self.get_child_ids(*id))
+                .copied()
+                .collect();
+            levels.push(level);
+            level = next_level;
+        }
+        levels
+    }
+
+    /// Makes the given node the new root of the tree by reversing the parent-child relationships
+    /// on the path from the current root to the new root. Node ids and values are preserved.
+    ///
+    /// # Arguments
+    ///
+    /// `new_root` - id of the node that should become the root
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new("COM");
+    /// let b = tree.add_child(0, "B");
+    /// let c = tree.add_child(b, "C");
+    /// let d = tree.add_child(0, "D");
+    /// tree.reroot(c);
+    /// assert_eq!(tree.get_root_id(), c);
+    /// assert_eq!(*tree.get_parent_id(c), None);
+    /// assert_eq!(*tree.get_parent_id(b), Some(c));
+    /// assert_eq!(*tree.get_parent_id(0), Some(b));
+    /// assert_eq!(*tree.get_parent_id(d), Some(0));
+    /// assert_eq!(tree.depths()[&d], 3);
+    /// ```
+    pub fn reroot(&mut self, new_root: usize) {
+        let mut path = vec![new_root];
+        while let Some(parent_id) = *self.get_parent_id(*path.last().unwrap()) {
+            path.push(parent_id);
+        }
+        for pair in path.windows(2) {
+            let (child_id, parent_id) = (pair[0], pair[1]);
+            let parent = self.get_mut_node(parent_id);
+            parent.remove_child(child_id);
+            parent.set_parent(child_id);
+            self.get_mut_node(child_id).add_child(parent_id);
+        }
+        self.get_mut_node(new_root).clear_parent();
+        self.root_id = new_root;
+    }
+
+    /// Prints a subtree to the console, indenting each node by two spaces per level of depth.
+    ///
+    /// # Arguments
+    ///
+    /// `start` - id of the node the output should start from
+    pub fn pretty_print(&self, start: usize)
+    where
+        T: Display,
+    {
+        print!("{}", self.pretty_string(start));
+    }
+
+    /// Renders a subtree into a string, indenting each node by two spaces per level of depth.
+    ///
+    /// # Arguments
+    ///
+    /// `start` - id of the node the output should start from
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new("/");
+    /// let dir = tree.add_child(0, "a");
+    /// tree.add_child(dir, "e");
+    /// tree.add_child(0, "b.txt");
+    /// assert_eq!(tree.pretty_string(0), "/\n  a\n    e\n  b.txt\n");
+    /// assert_eq!(tree.pretty_string(dir), "a\n  e\n");
+    /// ```
+    pub fn pretty_string(&self, start: usize) -> String
+    where
+        T: Display,
+    {
+        let mut output = String::new();
+        let mut stack = vec![(start, 0)];
+        while let Some((current_id, depth)) = stack.pop() {
+            let current_node = self.get_node(current_id);
+            output.push_str(&format!("{}{}\n", "  ".repeat(depth), current_node.val));
+            for child in current_node.get_child_ids().iter().rev() {
+                stack.push((*child, depth + 1));
+            }
+        }
+        output
+    }
+}
+
+impl Tree<String> {
+    /// Renders the whole tree into a string, indenting each node by two spaces per level of depth.
+    /// The output can be parsed back with [`Tree::from_indented`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut tree = aoc_lib::tree::Tree::new(String::from("/"));
+    /// let dir = tree.add_child(0, String::from("a"));
+    /// tree.add_child(dir, String::from("e"));
+    /// assert_eq!(tree.to_indented(), "/\n  a\n    e\n");
+    /// ```
+    pub fn to_indented(&self) -> String {
+        self.pretty_string(self.root_id)
+    }
+
+    /// Parses a tree from a string where each line is a node, indented by two spaces per level of depth.
+    /// The first line is the root node. Empty lines are ignored.
+    ///
+    /// # Returns
+    /// The parsed tree, or an error if the input is empty, contains more than one root node,
+    /// or a line is indented by an odd number of spaces or more than one level deeper than the previous line.
+    ///
+    /// # Examples
+    /// ```
+    /// let tree = aoc_lib::tree::Tree::from_indented("/\n  a\n    e\n  b.txt\n").unwrap();
+    /// assert_eq!(tree.get_node_count(), 4);
+    /// assert_eq!(tree.get_val(3), "b.txt");
+    /// assert_eq!(*tree.get_parent_id(2), Some(1));
+    /// assert_eq!(tree.to_indented(), "/\n  a\n    e\n  b.txt\n");
+    ///
+    /// assert!(aoc_lib::tree::Tree::from_indented("/\n    a").is_err());
+    /// ```
+    pub fn from_indented(s: &str) -> Result<Tree<String>, String> {
+        let mut tree: Option<Tree<String>> = None;
+        let mut path: Vec<usize> = vec![];
+        for (idx, line) in s.lines().enumerate().filter(|(_, line)| !line.is_empty()) {
+            let val = line.trim_start_matches(' ');
+            let indent = line.len() - val.len();
+            if indent % 2 != 0 {
+                return Err(format!(
+                    "Invalid indentation on line {idx}. Expected a multiple of 2. Found {indent}."
+                ));
+            }
+            let depth = indent / 2;
+            if depth > path.len() {
+                return Err(format!(
+                    "Invalid indentation on line {idx}. Expected at most depth {}. Found {depth}.",
+                    path.len()
+                ));
+            }
+            path.truncate(depth);
+            let id = match (&mut tree, path.last()) {
+                (None, _) => {
+                    tree = Some(Tree::new(String::from(val)));
+                    0
+                }
+                (Some(tree), Some(parent_id)) => tree.add_child(*parent_id, String::from(val)),
+                (Some(_), None) => {
+                    return Err(format!("Found a second root node on line {idx}."));
+                }
+            };
+            path.push(id);
+        }
+        tree.ok_or(String::from("Input contains no nodes."))
+    }
+}
+
+impl Tree<Option<i64>> {
+    /// Parses a tree from a bracketed list like `[[1,2],[3,[4,5]]]`. Every list becomes a node with the value `None`,
+    /// every number becomes a leaf with its value. Whitespace between elements is ignored.
+    ///
+    /// # Returns
+    /// The parsed tree, or an error if the brackets are malformed or the input contains unexpected characters.
+    ///
+    /// # Examples
+    /// ```
+    /// let tree = aoc_lib::tree::Tree::from_bracketed("[[1,2],[3,[4,5]]]").unwrap();
+    /// assert_eq!(tree.get_node_count(), 9);
+    /// assert_eq!(*tree.get_val(0), None);
+    /// assert_eq!(tree.child_vals(1), vec![&Some(1), &Some(2)]);
+    /// assert_eq!(tree.aggregate_root(|_, val| val.unwrap_or(0)), 15);
+    ///
+    /// assert!(aoc_lib::tree::Tree::from_bracketed("[[1,2]").is_err());
+    /// ```
+    pub fn from_bracketed(s: &str) -> Result<Tree<Option<i64>>, String> {
+        let bytes = s.as_bytes();
+        let mut pos = 0;
+        let mut tree: Option<Tree<Option<i64>>> = None;
+        // Ids of the lists that have been opened but not closed yet, innermost last.
+        let mut open_lists: Vec<usize> = vec![];
+        loop {
+            let val = parse_bracketed_token(bytes, &mut pos)?;
+            let id = match (tree.as_mut(), open_lists.last()) {
+                (Some(tree), Some(parent_id)) => tree.add_child(*parent_id, val),
+                _ => {
+                    tree = Some(Tree::new(val));
+                    0
+                }
+            };
+            if val.is_none() {
+                skip_whitespace(bytes, &mut pos);
+                if bytes.get(pos) != Some(&b']') {
+                    open_lists.push(id);
+                    continue;
+                }
+                pos += 1;
+            }
+            // A value is complete. Close finished lists until the next element or the end of the outermost list.
+            loop {
+                if open_lists.is_empty() {
+                    skip_whitespace(bytes, &mut pos);
+                    if pos < bytes.len() {
+                        return Err(format!("Unexpected character at position {pos}."));
+                    }
+                    return Ok(tree.unwrap());
+                }
+                skip_whitespace(bytes, &mut pos);
+                match bytes.get(pos) {
+                    Some(b',') => {
+                        pos += 1;
+                        break;
+                    }
+                    Some(b']') => {
+                        pos += 1;
+                        open_lists.pop();
+                    }
+                    Some(_) => return Err(format!("Unexpected character at position {pos}.")),
+                    None => return Err(String::from("Unexpected end of input.")),
+                }
+            }
+        }
+    }
+}
+
+/// Parses the start of a value in a bracketed list: `None` for an opening bracket, or the value of a number.
+fn parse_bracketed_token(bytes: &[u8], pos: &mut usize) -> Result<Option<i64>, String> {
+    skip_whitespace(bytes, pos);
+    match bytes.get(*pos) {
+        Some(b'[') => {
+            *pos += 1;
+            Ok(None)
+        }
+        Some(c) if c.is_ascii_digit() || *c == b'-' => {
+            let start = *pos;
+            *pos += 1;
+            while bytes.get(*pos).is_some_and(|c| c.is_ascii_digit()) {
+                *pos += 1;
+            }
+            let number = std::str::from_utf8(&bytes[start..*pos]).unwrap();
+            match number.parse() {
+                Ok(number) => Ok(Some(number)),
+                Err(_) => Err(format!("Invalid number at position {start}.")),
+            }
+        }
+        Some(_) => Err(format!("Unexpected character at position {pos}.")),
+        None => Err(String::from("Unexpected end of input.")),
+    }
+}
+
+fn skip_whitespace(bytes: &[u8], pos: &mut usize) {
+    while bytes.get(*pos).is_some_and(|c| c.is_ascii_whitespace()) {
+        *pos += 1;
     }
 }
 
 mod tree_node {
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
     pub struct TreeNode<T> {
         pub val: T,
         parent: Option<usize>,
@@ -148,7 +746,7 @@ mod tree_node {
         }
 
         pub fn has_children(&self) -> bool {
-            self.get_child_ids().len() > 0
+            !self.get_child_ids().is_empty()
         }
 
         pub fn get_child_ids(&self) -> &Vec<usize> {
@@ -162,6 +760,14 @@ mod tree_node {
         pub fn add_child(&mut self, child: usize) {
             self.children.push(child);
         }
+
+        pub fn remove_child(&mut self, child: usize) {
+            self.children.retain(|id| *id != child);
+        }
+
+        pub fn clear_parent(&mut self) {
+            self.parent = None;
+        }
     }
 }
 
@@ -181,6 +787,20 @@ mod tests {
         assert_eq!(8, *tree.get_val(child));
     }
 
+    #[test]
+    fn test_clone() {
+        let mut tree = Tree::new(String::from("root"));
+        let a = tree.add_child(0, String::from("a"));
+        let mut copy = tree.clone();
+        assert_eq!(tree, copy);
+        copy.add_child(a, String::from("b"));
+        assert_ne!(tree, copy);
+        assert_eq!(tree.get_node_count(), 2);
+        assert_eq!(copy.get_val(*copy.get_child_ids(a).first().unwrap()), "b");
+        copy.get_mut_val(a).push('!');
+        assert_eq!(tree.get_val(a), "a");
+    }
+
     #[test]
     fn test_struct_type() {
         #[allow(dead_code)]
@@ -213,4 +833,67 @@ mod tests {
         *tree.get_mut_val(0) = 8;
         assert_eq!(*tree.get_val(0), 8);
     }
+
+    #[test]
+    fn test_pretty_print() {
+        let mut tree = Tree::new(5);
+        let child = tree.add_child(0, 8);
+        tree.add_child(child, 20);
+        tree.pretty_print(0);
+    }
+
+    #[test]
+    fn test_post_order() {
+        let mut tree = Tree::new(1);
+        let a = tree.add_child(0, 2);
+        tree.add_child(a, 3);
+        tree.add_child(a, 4);
+        tree.add_child(0, 5);
+        let mut sizes: HashMap<usize, i32> = HashMap::new();
+        for id in tree.post_order(0) {
+            let children: i32 = tree.get_child_ids(id).iter().map(|c| sizes[c]).sum();
+            sizes.insert(id, children + tree.get_val(id));
+        }
+        assert_eq!(sizes[&0], 15);
+        assert_eq!(sizes[&a], 9);
+    }
+
+    #[test]
+    fn test_from_indented() {
+        let input = "/\n  a\n    e\n      i\n    f\n  b.txt\n  d\n    j\n";
+        let tree = Tree::from_indented(input).unwrap();
+        assert_eq!(tree.get_node_count(), 8);
+        assert_eq!(tree.to_indented(), input);
+
+        assert!(Tree::from_indented("").is_err());
+        assert!(Tree::from_indented("  /").is_err());
+        assert!(Tree::from_indented("/\n a").is_err());
+        assert!(Tree::from_indented("/\n  a\n      b").is_err());
+        assert!(Tree::from_indented("/\n  a\n/").is_err());
+    }
+
+    #[test]
+    fn test_from_bracketed() {
+        let tree = Tree::from_bracketed(" [ [], [-3, 10] ] ").unwrap();
+        assert_eq!(tree.get_node_count(), 5);
+        assert!(tree.get_child_ids(1).is_empty());
+        assert_eq!(tree.child_vals(2), vec![&Some(-3), &Some(10)]);
+
+        let tree = Tree::from_bracketed("7").unwrap();
+        assert_eq!(*tree.get_val(0), Some(7));
+
+        assert!(Tree::from_bracketed("").is_err());
+        assert!(Tree::from_bracketed("[1,2]]").is_err());
+        assert!(Tree::from_bracketed("[1,,2]").is_err());
+        assert!(Tree::from_bracketed("[1 2]").is_err());
+        assert!(Tree::from_bracketed("[-]").is_err());
+        assert!(Tree::from_bracketed("[a]").is_err());
+
+        let nested = "[".repeat(200_000) + &"]".repeat(200_000);
+        assert_eq!(
+            Tree::from_bracketed(&nested).unwrap().get_node_count(),
+            200_000
+        );
+        assert!(Tree::from_bracketed(&"[".repeat(200_000)).is_err());
+    }
 }
@@ -1,3 +1,6 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::hash::Hash;
+
 /// Calculate the manhattan distance between two points in 2D space.
 ///
 /// # Examples
@@ -21,3 +24,944 @@ pub fn manhattan_2d(a: (i32, i32), b: (i32, i32)) -> i32 {
 pub fn manhattan_3d(a: (i32, i32, i32), b: (i32, i32, i32)) -> i32 {
     (a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs()
 }
+
+/// Get the sign of a number: -1 if it is negative, 0 if it is zero and 1 if it is positive.
+///
+/// # Examples
+/// ```
+/// assert_eq!(aoc_lib::util::sign(-7), -1);
+/// assert_eq!(aoc_lib::util::sign(0), 0);
+/// assert_eq!(aoc_lib::util::sign(42), 1);
+/// ```
+pub fn sign(n: i32) -> i32 {
+    n.signum()
+}
+
+/// Get the direction of a single step from one point toward another. Each axis moves by at most one,
+/// so a diagonal step is taken if the points differ on both axes.
+///
+/// # Examples
+/// ```
+/// assert_eq!(aoc_lib::util::step_toward((0, 0), (5, -3)), (1, -1));
+/// assert_eq!(aoc_lib::util::step_toward((2, 4), (2, 0)), (0, -1));
+/// assert_eq!(aoc_lib::util::step_toward((1, 1), (1, 1)), (0, 0));
+/// ```
+pub fn step_toward(from: (i32, i32), to: (i32, i32)) -> (i32, i32) {
+    (sign(to.0 - from.0), sign(to.1 - from.1))
+}
+
+/// Move a rope knot after the knot ahead of it. The tail stays in place while it touches the head,
+/// including diagonally and when overlapping. Otherwise it takes one step toward the head, moving diagonally if they are not in the same row or column.
+///
+/// # Returns
+/// The new position of the tail.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::follow;
+/// assert_eq!(follow((1, 1), (0, 0)), (0, 0));
+/// assert_eq!(follow((2, 0), (0, 0)), (1, 0));
+/// assert_eq!(follow((2, 1), (0, 0)), (1, 1));
+///
+/// let mut rope = [(0, 0); 3];
+/// rope[0] = (2, 2);
+/// for i in 1..rope.len() {
+///     rope[i] = follow(rope[i - 1], rope[i]);
+/// }
+/// assert_eq!(rope, [(2, 2), (1, 1), (0, 0)]);
+/// ```
+pub fn follow(head: (i32, i32), tail: (i32, i32)) -> (i32, i32) {
+    if (head.0 - tail.0).abs() <= 1 && (head.1 - tail.1).abs() <= 1 {
+        return tail;
+    }
+    let (dx, dy) = step_toward(tail, head);
+    (tail.0 + dx, tail.1 + dy)
+}
+
+/// Split a number into its base 10 digits, starting with the most significant digit.
+///
+/// # Examples
+/// ```
+/// assert_eq!(aoc_lib::util::digits(2024), vec![2, 0, 2, 4]);
+/// assert_eq!(aoc_lib::util::digits(0), vec![0]);
+/// ```
+pub fn digits(n: u64) -> Vec<u8> {
+    let mut digits = vec![(n % 10) as u8];
+    let mut rest = n / 10;
+    while rest > 0 {
+        digits.push((rest % 10) as u8);
+        rest /= 10;
+    }
+    digits.reverse();
+    digits
+}
+
+/// Combine base 10 digits into a number, starting with the most significant digit. This is the inverse of [`digits`].
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::{digits, from_digits};
+/// assert_eq!(from_digits(&[2, 0, 2, 4]), 2024);
+/// assert_eq!(from_digits(&[0, 0, 7]), 7);
+/// assert_eq!(from_digits(&[]), 0);
+///
+/// let split = digits(253000);
+/// let (left, right) = split.split_at(split.len() / 2);
+/// assert_eq!((from_digits(left), from_digits(right)), (253, 0));
+/// ```
+pub fn from_digits(digits: &[u8]) -> u64 {
+    digits
+        .iter()
+        .fold(0, |number, digit| number * 10 + *digit as u64)
+}
+
+/// Calculate the median of a set of numbers. For an even count, this is the mean of the two middle numbers.
+/// The input will be sorted in place.
+///
+/// # Panics
+///
+/// Will panic if `nums` is empty.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::median;
+/// assert_eq!(median(&mut [16, 1, 2, 0, 4, 2, 7, 1, 2, 14]), 2.0);
+/// assert_eq!(median(&mut [3, 1, 2]), 2.0);
+/// assert_eq!(median(&mut [4, 1]), 2.5);
+/// ```
+pub fn median(nums: &mut [i64]) -> f64 {
+    if nums.is_empty() {
+        panic!("Can't calculate the median of an empty set.");
+    }
+    nums.sort();
+    let mid = nums.len() / 2;
+    match nums.len() % 2 {
+        0 => (nums[mid - 1] as f64 + nums[mid] as f64) / 2.0,
+        _ => nums[mid] as f64,
+    }
+}
+
+/// Find the most frequent element. If several elements are equally frequent, the one that appears first is returned.
+///
+/// # Returns
+/// The most frequent element, or `None` if `items` is empty.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::mode;
+/// assert_eq!(mode(&['0', '1', '1', '0', '1']), Some('1'));
+/// assert_eq!(mode(&[3, 5, 5, 3]), Some(3));
+/// assert_eq!(mode::<i32>(&[]), None);
+/// ```
+pub fn mode<T>(items: &[T]) -> Option<T>
+where
+    T: Hash + Eq + Clone,
+{
+    let mut counts: HashMap<&T, usize> = HashMap::new();
+    for item in items {
+        *counts.entry(item).or_default() += 1;
+    }
+    let mut best: Option<(&T, usize)> = None;
+    for item in items {
+        let count = counts[item];
+        if best.is_none_or(|(_, best_count)| count > best_count) {
+            best = Some((item, count));
+        }
+    }
+    best.map(|(item, _)| item.clone())
+}
+
+/// A type with an identity value and an associative operation combining two values, used by `aggregate_monoid`.
+/// Unlike aggregating with `Add`, this also works for collections like `Vec` and `String` and has a result for empty input.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::Monoid;
+/// assert_eq!(i32::empty().combine(5).combine(7), 12);
+/// assert_eq!(String::from("ab").combine(String::from("cd")), "abcd");
+/// assert_eq!(vec![1].combine(vec![2, 3]), vec![1, 2, 3]);
+/// ```
+pub trait Monoid {
+    /// Get the identity value, which leaves any value unchanged when combined with it.
+    fn empty() -> Self;
+
+    /// Combine two values into one.
+    fn combine(self, other: Self) -> Self;
+}
+
+macro_rules! impl_monoid_for_numbers {
+    ($($t:ty),*) => {
+        $(
+            impl Monoid for $t {
+                fn empty() -> Self {
+                    0 as $t
+                }
+
+                fn combine(self, other: Self) -> Self {
+                    self + other
+                }
+            }
+        )*
+    };
+}
+
+impl_monoid_for_numbers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+impl Monoid for String {
+    fn empty() -> Self {
+        String::new()
+    }
+
+    fn combine(mut self, other: Self) -> Self {
+        self.push_str(&other);
+        self
+    }
+}
+
+impl<T> Monoid for Vec<T> {
+    fn empty() -> Self {
+        vec![]
+    }
+
+    fn combine(mut self, mut other: Self) -> Self {
+        self.append(&mut other);
+        self
+    }
+}
+
+/// A disjoint-set structure over the elements `0..n`, using path compression and union by rank.
+///
+/// # Examples
+/// ```
+/// let mut sets = aoc_lib::util::UnionFind::new(5);
+/// assert_eq!(sets.count_sets(), 5);
+/// sets.union(0, 1);
+/// sets.union(3, 4);
+/// sets.union(1, 0);
+/// assert_eq!(sets.count_sets(), 3);
+/// assert_eq!(sets.find(0), sets.find(1));
+/// assert_eq!(sets.find(3), sets.find(4));
+/// assert_ne!(sets.find(0), sets.find(2));
+/// assert_ne!(sets.find(1), sets.find(4));
+/// ```
+pub struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u32>,
+    set_count: usize,
+}
+
+impl UnionFind {
+    /// Create a new structure where each of the `n` elements is in its own set.
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..n).collect(),
+            ranks: vec![0; n],
+            set_count: n,
+        }
+    }
+
+    /// Get the representative element of the set containing `i`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `i` is out of range.
+    pub fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = i;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merge the sets containing `a` and `b`.
+    ///
+    /// # Returns
+    /// `true` if the elements were in different sets before the call.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `a` or `b` is out of range.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+        if root_a == root_b {
+            return false;
+        }
+        match self.ranks[root_a].cmp(&self.ranks[root_b]) {
+            std::cmp::Ordering::Less => self.parents[root_a] = root_b,
+            std::cmp::Ordering::Greater => self.parents[root_b] = root_a,
+            std::cmp::Ordering::Equal => {
+                self.parents[root_b] = root_a;
+                self.ranks[root_a] += 1;
+            }
+        }
+        self.set_count -= 1;
+        true
+    }
+
+    /// Get the number of disjoint sets.
+    pub fn count_sets(&self) -> usize {
+        self.set_count
+    }
+}
+
+/// Merge overlapping inclusive ranges into a minimal set of disjoint ranges.
+/// Touching ranges, where the end of one range is directly followed by the start of the next one, are merged as well.
+/// The input will be sorted in place.
+///
+/// # Returns
+/// The merged ranges, sorted by their start.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::merge_intervals;
+/// assert_eq!(merge_intervals(&mut vec![(5, 8), (0, 2)]), vec![(0, 2), (5, 8)]);
+/// assert_eq!(merge_intervals(&mut vec![(0, 4), (3, 8), (10, 12)]), vec![(0, 8), (10, 12)]);
+/// assert_eq!(merge_intervals(&mut vec![(0, 10), (2, 3), (4, 5)]), vec![(0, 10)]);
+/// assert_eq!(merge_intervals(&mut vec![(0, 2), (3, 5)]), vec![(0, 5)]);
+/// assert_eq!(merge_intervals(&mut vec![]), vec![]);
+/// ```
+pub fn merge_intervals(intervals: &mut [(i64, i64)]) -> Vec<(i64, i64)> {
+    intervals.sort();
+    let mut merged: Vec<(i64, i64)> = vec![];
+    for &(start, end) in intervals.iter() {
+        match merged.last_mut() {
+            Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Get the inclusive range of x coordinates on a row that are within the given manhattan distance of a center position.
+///
+/// # Returns
+/// The covered range, or `None` if the row does not intersect the diamond.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::{diamond_row_interval, merge_intervals};
+/// assert_eq!(diamond_row_interval((8, 7), 9, 10), Some((2, 14)));
+/// assert_eq!(diamond_row_interval((8, 7), 9, -2), Some((8, 8)));
+/// assert_eq!(diamond_row_interval((8, 7), 9, 17), None);
+///
+/// let mut covered: Vec<(i64, i64)> = [((0, 0), 2), ((3, 1), 2)]
+///     .iter()
+///     .filter_map(|(center, radius)| diamond_row_interval(*center, *radius, 0))
+///     .collect();
+/// assert_eq!(merge_intervals(&mut covered), vec![(-2, 4)]);
+/// ```
+pub fn diamond_row_interval(center: (i64, i64), radius: i64, row: i64) -> Option<(i64, i64)> {
+    let remaining = radius - (row - center.1).abs();
+    match remaining >= 0 {
+        true => Some((center.0 - remaining, center.0 + remaining)),
+        false => None,
+    }
+}
+
+/// Apply a range mapping to a set of inclusive ranges.
+/// Parts of an input range that overlap the source range of a mapping are translated to the destination range,
+/// all other parts are passed through unchanged.
+///
+/// # Arguments
+/// * `input` - inclusive ranges that should be mapped
+/// * `map` - mapping entries in the form `(dest_start, src_start, len)`. Entries with a `len` of 0 or less are ignored.
+///
+/// # Returns
+/// The mapped ranges, merged with [`merge_intervals`].
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::apply_range_map;
+/// let map = [(50, 98, 2), (52, 50, 48)];
+/// assert_eq!(apply_range_map(&[(79, 92)], &map), vec![(81, 94)]);
+/// assert_eq!(apply_range_map(&[(0, 10)], &map), vec![(0, 10)]);
+/// assert_eq!(apply_range_map(&[(45, 55)], &map), vec![(45, 49), (52, 57)]);
+/// assert_eq!(apply_range_map(&[(96, 101)], &map), vec![(50, 51), (98, 101)]);
+/// assert_eq!(apply_range_map(&[(0, 10)], &[(100, 5, 0)]), vec![(0, 10)]);
+/// ```
+pub fn apply_range_map(input: &[(i64, i64)], map: &[(i64, i64, i64)]) -> Vec<(i64, i64)> {
+    let mut unmapped: Vec<(i64, i64)> = input.to_vec();
+    let mut mapped: Vec<(i64, i64)> = vec![];
+    for &(dest_start, src_start, len) in map {
+        if len <= 0 {
+            continue;
+        }
+        let src_end = src_start + len - 1;
+        let offset = dest_start - src_start;
+        let mut remaining = vec![];
+        for (start, end) in unmapped {
+            if end < src_start || start > src_end {
+                remaining.push((start, end));
+                continue;
+            }
+            if start < src_start {
+                remaining.push((start, src_start - 1));
+            }
+            if end > src_end {
+                remaining.push((src_end + 1, end));
+            }
+            mapped.push((start.max(src_start) + offset, end.min(src_end) + offset));
+        }
+        unmapped = remaining;
+    }
+    mapped.append(&mut unmapped);
+    merge_intervals(&mut mapped)
+}
+
+/// Reconstruct a path from a map of predecessors, as produced by a graph search.
+///
+/// # Arguments
+/// * `came_from` - maps every visited node to the node it was reached from. The start node has no entry.
+/// * `goal` - the node the path should end at
+///
+/// # Returns
+/// The nodes of the path, starting with the start node and ending with `goal`.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// let came_from = HashMap::from([((1, 0), (0, 0)), ((1, 1), (1, 0)), ((0, 1), (0, 0))]);
+/// assert_eq!(
+///     aoc_lib::util::reconstruct_path(&came_from, (1, 1)),
+///     vec![(0, 0), (1, 0), (1, 1)]
+/// );
+/// assert_eq!(aoc_lib::util::reconstruct_path(&came_from, (0, 0)), vec![(0, 0)]);
+/// ```
+pub fn reconstruct_path<K>(came_from: &HashMap<K, K>, goal: K) -> Vec<K>
+where
+    K: Hash + Eq + Clone,
+{
+    let mut path = vec![goal];
+    while let Some(previous) = came_from.get(path.last().unwrap()) {
+        path.push(previous.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// A simple memoization cache. Entries are never evicted.
+///
+/// # Examples
+/// ```
+/// let mut memo = aoc_lib::util::Memo::new();
+/// assert_eq!(memo.get_or_insert_with(5, || 25), 25);
+/// assert_eq!(memo.get_or_insert_with(5, || unreachable!()), 25);
+/// assert_eq!(memo.get(&5), Some(&25));
+/// assert_eq!(memo.get(&6), None);
+/// ```
+#[derive(Default)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    /// Create a new, empty cache.
+    pub fn new() -> Memo<K, V> {
+        Memo {
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Get the cached value for a key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Cache a value for a key, replacing any previous value.
+    pub fn insert(&mut self, key: K, val: V) {
+        self.cache.insert(key, val);
+    }
+
+    /// Get the cached value for a key, computing and caching it with `f` if it is not cached yet.
+    ///
+    /// # Returns
+    /// A copy of the cached value.
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> V
+    where
+        F: FnOnce() -> V,
+    {
+        self.cache.entry(key).or_insert_with(f).clone()
+    }
+}
+
+/// Split a sequence into consecutive chunks of the given size. The last chunk may be shorter.
+///
+/// # Panics
+///
+/// Will panic if `size` is 0.
+///
+/// # Examples
+/// ```
+/// let lines = ["a", "b", "c", "d", "e"];
+/// assert_eq!(aoc_lib::util::chunk(&lines, 2), vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]]);
+/// assert_eq!(aoc_lib::util::chunk(&lines[..0], 2), Vec::<Vec<&str>>::new());
+/// ```
+pub fn chunk<T: Clone>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    items.chunks(size).map(|chunk| chunk.to_vec()).collect()
+}
+
+/// Transpose a nested vector, turning rows into columns.
+///
+/// # Panics
+///
+/// Will panic if any row has a different length than the first one.
+///
+/// # Examples
+/// ```
+/// let rows = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// assert_eq!(aoc_lib::util::transpose_vec(rows), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+/// assert_eq!(aoc_lib::util::transpose_vec(Vec::<Vec<i32>>::new()), Vec::<Vec<i32>>::new());
+/// ```
+pub fn transpose_vec<T: Clone>(rows: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let width = match rows.first() {
+        Some(row) => row.len(),
+        None => return vec![],
+    };
+    for (idx, row) in rows.iter().enumerate() {
+        if row.len() != width {
+            panic!(
+                "Invalid length on row {idx}. Expected {width}. Found {}.",
+                row.len()
+            );
+        }
+    }
+    (0..width)
+        .map(|x| rows.iter().map(|row| row[x].clone()).collect())
+        .collect()
+}
+
+/// Run a breadth-first search over arbitrary states.
+///
+/// # Arguments
+/// * `start` - the state the search starts from
+/// * `neighbors` - a closure returning the states reachable in one step from a state
+///
+/// # Returns
+/// The number of steps needed to reach every reachable state.
+///
+/// # Examples
+/// ```
+/// let distances = aoc_lib::util::bfs(1, |n| vec![n * 2, n + 3].into_iter().filter(|m| *m < 20));
+/// assert_eq!(distances[&1], 0);
+/// assert_eq!(distances[&4], 1);
+/// assert_eq!(distances[&11], 3);
+/// assert!(!distances.contains_key(&3));
+/// ```
+pub fn bfs<S, F, I>(start: S, neighbors: F) -> HashMap<S, usize>
+where
+    S: Hash + Eq + Clone,
+    F: Fn(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[&current];
+        for next in neighbors(&current) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// Run a breadth-first search over arbitrary states until a goal state is found.
+///
+/// # Arguments
+/// * `start` - the state the search starts from
+/// * `is_goal` - a closure returning true if a state is a goal state
+/// * `neighbors` - a closure returning the states reachable in one step from a state
+///
+/// # Returns
+/// A shortest path from `start` to the first goal state found, including both ends, or `None` if no goal state is reachable.
+///
+/// # Examples
+/// ```
+/// let neighbors = |n: &i32| vec![n * 2, n + 3].into_iter().filter(|m| *m < 20);
+/// assert_eq!(aoc_lib::util::bfs_path(1, |n| *n == 11, neighbors), Some(vec![1, 4, 8, 11]));
+/// assert_eq!(aoc_lib::util::bfs_path(1, |n| *n == 3, neighbors), None);
+/// ```
+pub fn bfs_path<S, G, F, I>(start: S, is_goal: G, neighbors: F) -> Option<Vec<S>>
+where
+    S: Hash + Eq + Clone,
+    G: Fn(&S) -> bool,
+    F: Fn(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut came_from: HashMap<S, S> = HashMap::new();
+    let mut queue = VecDeque::from([start.clone()]);
+    while let Some(current) = queue.pop_front() {
+        if is_goal(&current) {
+            return Some(reconstruct_path(&came_from, current));
+        }
+        for next in neighbors(&current) {
+            if next != start && !came_from.contains_key(&next) {
+                came_from.insert(next.clone(), current.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Find the smallest value in the inclusive range `[lo, hi]` for which a predicate holds.
+/// The predicate must be monotonic: once it is true for a value, it has to be true for all larger values as well.
+/// Otherwise the result is unspecified.
+///
+/// # Returns
+/// The smallest value satisfying the predicate, or `None` if it is false for every value in the range.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::binary_search;
+/// assert_eq!(binary_search(0, 100, |n| n * n >= 50), Some(8));
+/// assert_eq!(binary_search(-10, 10, |_| true), Some(-10));
+/// assert_eq!(binary_search(0, 100, |n| n > 100), None);
+/// assert_eq!(binary_search(5, 4, |_| true), None);
+/// assert_eq!(binary_search(i64::MIN, i64::MAX, |n| n >= 0), Some(0));
+/// assert_eq!(binary_search(i64::MIN, i64::MAX, |n| n >= i64::MAX), Some(i64::MAX));
+/// ```
+pub fn binary_search<F>(lo: i64, hi: i64, pred: F) -> Option<i64>
+where
+    F: Fn(i64) -> bool,
+{
+    if lo > hi || !pred(hi) {
+        return None;
+    }
+    let (mut lo, mut hi) = (lo, hi);
+    while lo < hi {
+        // Rounds down like (lo + hi) / 2 would, without overflowing for ranges wider than i64::MAX.
+        let mid = (lo >> 1) + (hi >> 1) + (lo & hi & 1);
+        match pred(mid) {
+            true => hi = mid,
+            false => lo = mid + 1,
+        }
+    }
+    Some(lo)
+}
+
+/// Sort nodes so that every node comes before all nodes it has an edge to.
+/// Among nodes that could come next, the one that appears first in `edges` is picked, so the result is deterministic.
+///
+/// # Arguments
+/// * `edges` - directed edges in the form `(before, after)`
+///
+/// # Returns
+/// The sorted nodes, or the nodes on and between cycles if the edges contain a cycle, in order of their first appearance.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::topo_sort;
+/// assert_eq!(topo_sort(&[(47, 53), (97, 13), (97, 47), (47, 13)]), Ok(vec![97, 47, 53, 13]));
+/// assert_eq!(topo_sort(&[('a', 'b'), ('b', 'c'), ('c', 'b'), ('c', 'd')]), Err(vec!['b', 'c']));
+/// assert_eq!(topo_sort::<i32>(&[]), Ok(vec![]));
+/// ```
+pub fn topo_sort<N>(edges: &[(N, N)]) -> Result<Vec<N>, Vec<N>>
+where
+    N: Hash + Eq + Clone,
+{
+    let mut ids: HashMap<N, usize> = HashMap::new();
+    let mut nodes: Vec<N> = vec![];
+    let mut get_id = |node: &N| match ids.get(node) {
+        Some(id) => *id,
+        None => {
+            ids.insert(node.clone(), nodes.len());
+            nodes.push(node.clone());
+            nodes.len() - 1
+        }
+    };
+    let edges: Vec<(usize, usize)> = edges
+        .iter()
+        .map(|(before, after)| (get_id(before), get_id(after)))
+        .collect();
+
+    let mut successors = vec![vec![]; nodes.len()];
+    let mut in_degrees = vec![0; nodes.len()];
+    for &(before, after) in &edges {
+        successors[before].push(after);
+        in_degrees[after] += 1;
+    }
+    let mut ready: BTreeSet<usize> = (0..nodes.len()).filter(|id| in_degrees[*id] == 0).collect();
+    let mut order = vec![];
+    while let Some(id) = ready.pop_first() {
+        order.push(id);
+        for &next in &successors[id] {
+            in_degrees[next] -= 1;
+            if in_degrees[next] == 0 {
+                ready.insert(next);
+            }
+        }
+    }
+    if order.len() == nodes.len() {
+        return Ok(order.into_iter().map(|id| nodes[id].clone()).collect());
+    }
+
+    // Nodes left with incoming edges are on cycles or reachable from them. Trim those that don't lead back into a cycle.
+    let mut remaining: Vec<bool> = in_degrees.iter().map(|degree| *degree > 0).collect();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for id in 0..nodes.len() {
+            if remaining[id] && !successors[id].iter().any(|next| remaining[*next]) {
+                remaining[id] = false;
+                changed = true;
+            }
+        }
+    }
+    Err((0..nodes.len())
+        .filter(|id| remaining[*id])
+        .map(|id| nodes[id].clone())
+        .collect())
+}
+
+/// Get all orderings of the given items. Equal items are not deduplicated, so `n` items always yield `n!` permutations.
+/// For large inputs use `permutations_iter` instead, which produces the permutations one at a time.
+///
+/// # Examples
+/// ```
+/// let mut perms = aoc_lib::util::permutations(&[1, 2, 3]);
+/// assert_eq!(perms[0], vec![1, 2, 3]);
+/// perms.sort();
+/// assert_eq!(
+///     perms,
+///     vec![
+///         vec![1, 2, 3],
+///         vec![1, 3, 2],
+///         vec![2, 1, 3],
+///         vec![2, 3, 1],
+///         vec![3, 1, 2],
+///         vec![3, 2, 1]
+///     ]
+/// );
+/// assert_eq!(aoc_lib::util::permutations::<i32>(&[]), vec![Vec::<i32>::new()]);
+/// ```
+pub fn permutations<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    permutations_iter(items).collect()
+}
+
+/// Get a lazy iterator over all orderings of the given items, generated with Heap's algorithm.
+/// The first permutation is the input itself; each following one differs from the previous by a single swap.
+///
+/// # Examples
+/// ```
+/// let cities = ["London", "Dublin", "Belfast"];
+/// let mut perms = aoc_lib::util::permutations_iter(&cities);
+/// assert_eq!(perms.next(), Some(vec!["London", "Dublin", "Belfast"]));
+/// assert_eq!(perms.next(), Some(vec!["Dublin", "London", "Belfast"]));
+/// assert_eq!(perms.count(), 4);
+/// ```
+pub fn permutations_iter<T: Clone>(items: &[T]) -> Permutations<T> {
+    Permutations {
+        items: items.to_vec(),
+        counters: vec![0; items.len()],
+        position: 1,
+        started: false,
+    }
+}
+
+/// An iterator over the permutations of a sequence, as returned by `permutations_iter`.
+pub struct Permutations<T> {
+    items: Vec<T>,
+    counters: Vec<usize>,
+    position: usize,
+    started: bool,
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if !self.started {
+            self.started = true;
+            return Some(self.items.clone());
+        }
+        while self.position < self.items.len() {
+            let i = self.position;
+            if self.counters[i] < i {
+                match i % 2 {
+                    0 => self.items.swap(0, i),
+                    _ => self.items.swap(self.counters[i], i),
+                }
+                self.counters[i] += 1;
+                self.position = 1;
+                return Some(self.items.clone());
+            }
+            self.counters[i] = 0;
+            self.position += 1;
+        }
+        None
+    }
+}
+
+/// Get all subsets of `k` items, each in the order of the input. The subsets are ordered by the indices of their items.
+/// For large inputs use `combinations_iter` instead, which produces the subsets one at a time.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::combinations;
+/// assert_eq!(
+///     combinations(&['a', 'b', 'c', 'd'], 2),
+///     vec![
+///         vec!['a', 'b'],
+///         vec!['a', 'c'],
+///         vec!['a', 'd'],
+///         vec!['b', 'c'],
+///         vec!['b', 'd'],
+///         vec!['c', 'd']
+///     ]
+/// );
+/// assert_eq!(combinations(&[1, 2], 0), vec![Vec::<i32>::new()]);
+/// assert!(combinations(&[1, 2], 3).is_empty());
+/// ```
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    combinations_iter(items, k).collect()
+}
+
+/// Get a lazy iterator over all subsets of `k` items, in the same order as `combinations`.
+///
+/// # Examples
+/// ```
+/// let entries = [1721, 979, 366, 299, 675, 1456];
+/// let triple = aoc_lib::util::combinations_iter(&entries, 3)
+///     .find(|triple| triple.iter().sum::<i32>() == 2020);
+/// assert_eq!(triple, Some(vec![979, 366, 675]));
+/// ```
+pub fn combinations_iter<T: Clone>(items: &[T], k: usize) -> Combinations<T> {
+    Combinations {
+        items: items.to_vec(),
+        indices: (0..k).collect(),
+        started: false,
+    }
+}
+
+/// An iterator over the subsets of a fixed size of a sequence, as returned by `combinations_iter`.
+pub struct Combinations<T> {
+    items: Vec<T>,
+    indices: Vec<usize>,
+    started: bool,
+}
+
+impl<T: Clone> Iterator for Combinations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let n = self.items.len();
+        let k = self.indices.len();
+        if k > n {
+            return None;
+        }
+        if self.started {
+            // Advance the rightmost index that can still move, then reset all indices behind it.
+            let i = (0..k).rev().find(|i| self.indices[*i] != i + n - k)?;
+            self.indices[i] += 1;
+            for j in i + 1..k {
+                self.indices[j] = self.indices[j - 1] + 1;
+            }
+        }
+        self.started = true;
+        Some(
+            self.indices
+                .iter()
+                .map(|i| self.items[*i].clone())
+                .collect(),
+        )
+    }
+}
+
+/// Get the n-th permutation of the given items in lexicographic order without generating the ones before it.
+/// The items are expected to be distinct. Their input order does not matter.
+///
+/// # Arguments
+///
+/// `items` - the items to permute
+/// `n` - the 0-based index of the permutation
+///
+/// # Returns
+/// The permutation, or `None` if `n` is not smaller than the number of permutations.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::nth_permutation;
+/// let items = ['c', 'a', 'b'];
+/// assert_eq!(nth_permutation(&items, 0), Some(vec!['a', 'b', 'c']));
+/// assert_eq!(nth_permutation(&items, 3), Some(vec!['b', 'c', 'a']));
+/// assert_eq!(nth_permutation(&items, 5), Some(vec!['c', 'b', 'a']));
+/// assert_eq!(nth_permutation(&items, 6), None);
+/// assert_eq!(nth_permutation(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], 999_999).unwrap()[0], 2);
+/// ```
+pub fn nth_permutation<T: Clone + Ord>(items: &[T], n: usize) -> Option<Vec<T>> {
+    // factorials[i] is None if i! does not fit into usize, in which case any n is in range.
+    let mut factorials = vec![Some(1usize)];
+    for i in 1..=items.len() {
+        factorials.push(factorials[i - 1].and_then(|f| f.checked_mul(i)));
+    }
+    if factorials[items.len()].is_some_and(|count| n >= count) {
+        return None;
+    }
+    let mut remaining = items.to_vec();
+    remaining.sort();
+    let mut n = n;
+    let mut permutation = Vec::with_capacity(items.len());
+    for i in (0..items.len()).rev() {
+        let idx = match factorials[i] {
+            Some(f) => {
+                let idx = n / f;
+                n %= f;
+                idx
+            }
+            None => 0,
+        };
+        permutation.push(remaining.remove(idx));
+    }
+    Some(permutation)
+}
+
+/// Get the 0-based index of a permutation in the lexicographic order of all permutations of its items.
+/// This is the inverse of `nth_permutation`. The items are expected to be distinct.
+///
+/// # Returns
+/// The index of the permutation, or `None` if it does not fit into `usize`.
+///
+/// # Examples
+/// ```
+/// use aoc_lib::util::{nth_permutation, permutation_rank};
+/// assert_eq!(permutation_rank(&['a', 'b', 'c']), Some(0));
+/// assert_eq!(permutation_rank(&['b', 'c', 'a']), Some(3));
+/// let items = [4, 1, 3, 2, 0];
+/// assert_eq!(nth_permutation(&items, permutation_rank(&items).unwrap()), Some(items.to_vec()));
+///
+/// let sorted: Vec<u32> = (0..30).collect();
+/// assert_eq!(permutation_rank(&sorted), Some(0));
+/// let reversed: Vec<u32> = (0..30).rev().collect();
+/// assert_eq!(permutation_rank(&reversed), None);
+/// ```
+pub fn permutation_rank<T: Ord>(permutation: &[T]) -> Option<usize> {
+    let len = permutation.len();
+    let mut rank: usize = 0;
+    // factorial is None once it no longer fits into usize. It then only matters if an item is out of order.
+    let mut factorial = Some(1usize);
+    for i in (0..len).rev() {
+        let smaller_after = permutation[i + 1..]
+            .iter()
+            .filter(|item| **item < permutation[i])
+            .count();
+        if smaller_after > 0 {
+            rank = rank.checked_add(factorial?.checked_mul(smaller_after)?)?;
+        }
+        factorial = factorial.and_then(|f| f.checked_mul(len - i));
+    }
+    Some(rank)
+}